@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use super::runner;
 use crate::templates;
 
 pub fn add_day(day: u8) -> Result<()> {
@@ -31,6 +32,10 @@ pub fn add_day(day: u8) -> Result<()> {
     fs::write(day_path.join("Cargo.toml"), cargo_content)
         .context(format!("Failed to write {day_name}/Cargo.toml"))?;
 
+    let lib_content = lib_template(day).replace("{DAY}", &format!("{day:02}"));
+    fs::write(src_path.join("lib.rs"), lib_content)
+        .context(format!("Failed to write {day_name}/src/lib.rs"))?;
+
     let main_content = templates::DAY_MAIN_RS.replace("{DAY}", &format!("{day:02}"));
     fs::write(src_path.join("main.rs"), main_content)
         .context(format!("Failed to write {day_name}/src/main.rs"))?;
@@ -38,18 +43,35 @@ pub fn add_day(day: u8) -> Result<()> {
     fs::write(day_path.join("input.txt"), "")
         .context(format!("Failed to write {day_name}/input.txt"))?;
 
+    let answers_content = templates::DAY_ANSWERS.replace("{DAY}", &format!("{day:02}"));
+    fs::write(day_path.join("answers.txt"), answers_content)
+        .context(format!("Failed to write {day_name}/answers.txt"))?;
+
     let readme_content = templates::DAY_README.replace("{DAY}", &format!("{day:02}"));
     fs::write(day_path.join("README.md"), readme_content)
         .context(format!("Failed to write {day_name}/README.md"))?;
 
+    runner::sync_runner(Path::new("."))?;
+
     println!("{day_name} added successfully!");
     println!("\nNew structure:");
     println!("  {day_name}/");
     println!("  ├── Cargo.toml");
     println!("  ├── README.md");
+    println!("  ├── answers.txt");
     println!("  ├── src/");
+    println!("  │   ├── lib.rs");
     println!("  │   └── main.rs");
     println!("  └── input.txt");
 
     Ok(())
 }
+
+/// The `lib.rs` a new day starts from. Day 25 has no part 2.
+pub(super) const fn lib_template(day: u8) -> &'static str {
+    if day == 25 {
+        templates::DAY_LIB_SINGLE_PART_RS
+    } else {
+        templates::DAY_LIB_RS
+    }
+}
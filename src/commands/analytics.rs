@@ -1,82 +1,238 @@
 use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
-#[derive(Clone)]
-struct DayTiming {
-    day: u8,
-    part1_micros: u128,
-    part2_micros: u128,
+use super::answers::NO_PART;
+use super::build;
+use super::config::BuildSettings;
+use super::history;
+use super::process::{self, ResourceUsage};
+use super::report::OutputFormat;
+use super::summary::{BudgetLimit, Summary};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayStatus {
+    #[default]
+    Ok,
+    Timeout,
+    Failed,
+    /// At least one part panicked; the parts that finished are still timed.
+    Panicked,
+}
+
+/// A panic caught by `run_solution!`, reported on a `PANIC:<stage>:<message>` line.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PartPanic {
+    /// `parse`, `part 1` or `part 2`.
+    pub stage: String,
+    pub message: String,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct DayTiming {
+    pub day: u8,
+    #[serde(default)]
+    pub status: DayStatus,
+    pub part1_micros: Option<u128>,
+    pub part2_micros: Option<u128>,
+    /// The puzzle has no part 2 (`utils::NoPart`), so there is nothing to time.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub no_part2: bool,
+    pub peak_rss_kb: Option<u64>,
+    pub cpu_micros: Option<u128>,
+    /// Why the day failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub panics: Vec<PartPanic>,
 }
 
 impl DayTiming {
-    const fn total_micros(&self) -> u128 {
-        self.part1_micros + self.part2_micros
+    fn timed_out(day: u8, usage: Option<ResourceUsage>) -> Self {
+        Self {
+            day,
+            status: DayStatus::Timeout,
+            part1_micros: None,
+            part2_micros: None,
+            no_part2: false,
+            peak_rss_kb: usage.map(|usage| usage.peak_rss_kb),
+            cpu_micros: usage.map(|usage| usage.cpu_micros),
+            error: None,
+            panics: Vec::new(),
+        }
     }
 
-    #[allow(clippy::cast_precision_loss)]
-    fn part1_ms(&self) -> f64 {
-        self.part1_micros as f64 / 1000.0
+    fn failed(day: u8, error: String, usage: Option<ResourceUsage>) -> Self {
+        Self {
+            status: DayStatus::Failed,
+            error: Some(error),
+            ..Self::timed_out(day, usage)
+        }
     }
 
-    #[allow(clippy::cast_precision_loss)]
-    fn part2_ms(&self) -> f64 {
-        self.part2_micros as f64 / 1000.0
+    /// Keeps the timings of the parts that finished, if any.
+    fn panicked(
+        day: u8,
+        panics: Vec<PartPanic>,
+        finished: Option<Self>,
+        usage: Option<ResourceUsage>,
+    ) -> Self {
+        Self {
+            status: DayStatus::Panicked,
+            panics,
+            ..finished.unwrap_or_else(|| Self::timed_out(day, usage))
+        }
+    }
+
+    pub fn total_micros(&self) -> Option<u128> {
+        if self.no_part2 {
+            return self.part1_micros;
+        }
+
+        Some(self.part1_micros? + self.part2_micros?)
+    }
+
+    pub fn part1_ms(&self) -> Option<f64> {
+        self.part1_micros.map(micros_to_ms)
+    }
+
+    pub fn part2_ms(&self) -> Option<f64> {
+        self.part2_micros.map(micros_to_ms)
+    }
+
+    pub fn total_ms(&self) -> Option<f64> {
+        self.total_micros().map(micros_to_ms)
+    }
+
+    pub fn cpu_ms(&self) -> Option<f64> {
+        self.cpu_micros.map(micros_to_ms)
     }
 
     #[allow(clippy::cast_precision_loss)]
-    fn total_ms(&self) -> f64 {
-        self.total_micros() as f64 / 1000.0
+    pub fn peak_rss_mb(&self) -> Option<f64> {
+        self.peak_rss_kb.map(|kb| kb as f64 / 1024.0)
     }
 }
 
-pub fn run_analytics(file_path: &str) -> Result<()> {
+#[allow(clippy::cast_precision_loss)]
+pub fn micros_to_ms(micros: u128) -> f64 {
+    micros as f64 / 1000.0
+}
+
+pub fn run_analytics(
+    file_path: Option<&str>,
+    format: Option<OutputFormat>,
+    timeout: Option<Duration>,
+    budget: &[BudgetLimit],
+) -> Result<()> {
     if !Path::new("Cargo.toml").exists() {
         anyhow::bail!("Not in a workspace directory. Run 'aoc-cli init <name>' first.");
     }
 
-    println!("Running analytics on all day crates...\n");
+    let days: Vec<u8> = (1..=25)
+        .filter(|day| Path::new(&format!("day{day:02}")).exists())
+        .collect();
+
+    if days.is_empty() {
+        anyhow::bail!("No day crates found");
+    }
+
+    let settings = BuildSettings::load(Path::new("."))?;
+    // Before the report and history are written, so they do not make it dirty
+    let commit = history::current_commit();
+
+    println!("Building all day crates ({settings})...\n");
+
+    let build = build::build_workspace(Path::new("."), settings)?;
+
+    for (crate_name, errors) in &build.errors {
+        eprintln!("\nCompile errors in {crate_name}:");
+        for error in errors {
+            eprintln!("{}", error.trim_end());
+        }
+    }
+
+    println!("\nRunning analytics on all day crates...\n");
 
     let mut timings = Vec::new();
 
-    for day in 1..=25 {
+    for day in days {
         let day_name = format!("day{day:02}");
-        let day_path = Path::new(&day_name);
 
-        if !day_path.exists() {
+        if build.errors_for(&day_name).is_some() {
+            eprintln!("Warning: {day_name} failed to compile, skipping...\n");
+
             continue;
         }
 
+        let Some(binary) = build.binary(&day_name) else {
+            eprintln!(
+                "Warning: {day_name} was not built (a dependency failed to compile), skipping...\n"
+            );
+
+            continue;
+        };
+
         println!("Running {day_name}...");
 
-        let output = Command::new("cargo")
-            .args(["run", "--release", "-p", &day_name, "--", "--timing"])
-            .output()
-            .context(format!("Failed to run {day_name}"))?;
+        let output = process::run_measured(
+            Command::new(binary).arg("--timing").current_dir(&day_name),
+            timeout,
+        )
+        .context(format!("Failed to run {day_name}"))?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if output.timed_out() {
+            eprintln!("Warning: {day_name} timed out, recording as timeout...\n");
+            timings.push(DayTiming::timed_out(day, output.usage));
 
-            if !stderr.is_empty() {
-                eprintln!("Error output from {day_name}:");
-                eprintln!("{}", stderr.trim());
+            continue;
+        }
+
+        let panics = parse_panics(&output.stdout);
+        if !panics.is_empty() {
+            for panic in &panics {
+                eprintln!("Error in {day_name} {}: {}", panic.stage, panic.message);
             }
 
-            eprintln!("Warning: {day_name} failed to run, skipping...");
+            eprintln!("Warning: {day_name} panicked, recording the parts that finished...\n");
+            let finished = parse_timing(&output.stdout, day, output.usage);
+            timings.push(DayTiming::panicked(day, panics, finished, output.usage));
 
             continue;
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.success() {
+            if !output.stderr.is_empty() {
+                eprintln!("Error output from {day_name}:");
+                eprintln!("{}", output.stderr.trim());
+            }
+
+            eprintln!("Warning: {day_name} failed to run, recording as failure...\n");
+            timings.push(DayTiming::failed(day, output.error_message(), output.usage));
 
-        if let Some(timing) = parse_timing(&stdout, day) {
+            continue;
+        }
+
+        if let Some(timing) = parse_timing(&output.stdout, day, output.usage) {
+            let part2 = if timing.no_part2 {
+                NO_PART.to_string()
+            } else {
+                console_ms(timing.part2_ms())
+            };
             println!(
-                "  Part 1: {:.3}ms | Part 2: {:.3}ms | Total: {:.3}ms\n",
-                timing.part1_ms(),
-                timing.part2_ms(),
-                timing.total_ms()
+                "  Part 1: {} | Part 2: {part2} | Total: {}",
+                console_ms(timing.part1_ms()),
+                console_ms(timing.total_ms())
             );
+            if let (Some(peak_rss_mb), Some(cpu_ms)) = (timing.peak_rss_mb(), timing.cpu_ms()) {
+                println!("  Peak memory: {peak_rss_mb:.1}MB | CPU time: {cpu_ms:.3}ms");
+            }
+            println!();
             timings.push(timing);
         } else {
             eprintln!("Warning: Could not parse timing for {day_name}, skipping...\n");
@@ -84,28 +240,62 @@ pub fn run_analytics(file_path: &str) -> Result<()> {
     }
 
     if timings.is_empty() {
-        anyhow::bail!("No day crates found or all failed to run");
+        anyhow::bail!("All day crates failed to build or run");
     }
 
-    let table = format_table(&timings);
-    fs::write(file_path, table).context("Failed to write analytics file")?;
+    let summary = Summary::new(&timings, budget);
+    println!("{}", summary.to_markdown());
+
+    let (file_path, format) = resolve_output(file_path, format);
+    let report = format.render(&timings, settings, &summary)?;
+    fs::write(&file_path, report).context("Failed to write analytics file")?;
+
+    println!("Analytics written to {}", file_path.display());
 
-    println!("Analytics written to {file_path}");
+    history::record_run(Path::new("."), &timings, settings, commit)?;
+
+    if summary.budget_exceeded() {
+        anyhow::bail!(
+            "Performance budget exceeded ({} violation(s))",
+            summary.violations.len()
+        );
+    }
 
     Ok(())
 }
 
-fn parse_timing(output: &str, day: u8) -> Option<DayTiming> {
+/// A time on the console line of a day; a part without one failed.
+fn console_ms(ms: Option<f64>) -> String {
+    ms.map_or_else(|| "failed".to_string(), |ms| format!("{ms:.3}ms"))
+}
+
+pub(super) fn parse_timing(
+    output: &str,
+    day: u8,
+    usage: Option<ResourceUsage>,
+) -> Option<DayTiming> {
     for line in output.lines() {
         if let Some(timing_str) = line.strip_prefix("TIMING:") {
             let parts: Vec<&str> = timing_str.split(':').collect();
             if parts.len() == 2 {
-                let part1_micros = parts[0].parse().ok()?;
-                let part2_micros = parts[1].parse().ok()?;
+                let part1_micros = parse_part_micros(parts[0]).ok()?;
+                // `-` for puzzles without a part 2
+                let no_part2 = parts[1] == "-";
+                let part2_micros = if no_part2 {
+                    None
+                } else {
+                    parse_part_micros(parts[1]).ok()?
+                };
                 return Some(DayTiming {
                     day,
+                    status: DayStatus::Ok,
                     part1_micros,
                     part2_micros,
+                    no_part2,
+                    peak_rss_kb: usage.map(|usage| usage.peak_rss_kb),
+                    cpu_micros: usage.map(|usage| usage.cpu_micros),
+                    error: None,
+                    panics: Vec::new(),
                 });
             }
         }
@@ -113,22 +303,122 @@ fn parse_timing(output: &str, day: u8) -> Option<DayTiming> {
     None
 }
 
-fn format_table(timings: &[DayTiming]) -> String {
-    use std::fmt::Write;
+/// A part's field of the `TIMING:` line: microseconds, or `failed` if the part
+/// returned an error or panicked.
+fn parse_part_micros(field: &str) -> Result<Option<u128>, ParseIntError> {
+    if field == "failed" {
+        Ok(None)
+    } else {
+        field.parse().map(Some)
+    }
+}
 
-    let mut table = String::from("| day | part 1  | part 2  | total   |\n");
-    table.push_str("| --- | ------- | ------- | ------- |\n");
+pub(super) fn parse_panics(output: &str) -> Vec<PartPanic> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix("PANIC:")?.split_once(':'))
+        .map(|(stage, message)| PartPanic {
+            stage: stage.to_string(),
+            message: message.to_string(),
+        })
+        .collect()
+}
 
-    for timing in timings {
-        let _ = writeln!(
-            &mut table,
-            "| {:>3} | {:>6.2}ms | {:>6.2}ms | {:>6.2}ms |",
-            timing.day,
-            timing.part1_ms(),
-            timing.part2_ms(),
-            timing.total_ms()
+/// Picks the output path and format: an explicit `--format` wins, otherwise the
+/// format is detected from the file extension, falling back to Markdown.
+fn resolve_output(
+    file_path: Option<&str>,
+    format: Option<OutputFormat>,
+) -> (PathBuf, OutputFormat) {
+    match (file_path, format) {
+        (Some(path), Some(format)) => (PathBuf::from(path), format),
+        (Some(path), None) => {
+            let path = PathBuf::from(path);
+            let format = OutputFormat::from_path(&path).unwrap_or(OutputFormat::Markdown);
+            (path, format)
+        }
+        (None, format) => {
+            let format = format.unwrap_or(OutputFormat::Markdown);
+            (
+                PathBuf::from(format!("analytics.{}", format.extension())),
+                format,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timing() {
+        let timing = parse_timing("Part 1: 1 (1.5ms)\nTIMING:1500:200\n", 3, None).unwrap();
+        assert_eq!(timing.day, 3);
+        assert_eq!(timing.status, DayStatus::Ok);
+        assert_eq!(
+            (timing.part1_micros, timing.part2_micros),
+            (Some(1500), Some(200))
         );
+        assert_eq!(timing.total_micros(), Some(1700));
+
+        let timing = parse_timing("TIMING:1500:-\n", 25, None).unwrap();
+        assert!(timing.no_part2);
+        assert_eq!(timing.part2_micros, None);
+        assert_eq!(timing.total_micros(), Some(1500));
+
+        let timing = parse_timing("TIMING:failed:200\n", 3, None).unwrap();
+        assert_eq!(
+            (timing.part1_micros, timing.part2_micros),
+            (None, Some(200))
+        );
+        assert_eq!(timing.total_micros(), None);
+
+        assert!(parse_timing("Part 1: 1\n", 3, None).is_none());
+        assert!(parse_timing("TIMING:1500\n", 3, None).is_none());
+        assert!(parse_timing("TIMING:abc:200\n", 3, None).is_none());
+    }
+
+    #[test]
+    fn test_parse_part_micros() {
+        assert_eq!(parse_part_micros("1500"), Ok(Some(1500)));
+        assert_eq!(parse_part_micros("failed"), Ok(None));
+        assert!(parse_part_micros("-").is_err());
+        assert!(parse_part_micros("").is_err());
     }
 
-    table
+    #[test]
+    fn test_parse_panics() {
+        let output = "Part 2: 4\nPANIC:part 1:panicked at src/lib.rs:3:5: a: b\nPANIC:broken\n";
+        let panics = parse_panics(output);
+
+        assert_eq!(panics.len(), 1);
+        assert_eq!(panics[0].stage, "part 1");
+        assert_eq!(panics[0].message, "panicked at src/lib.rs:3:5: a: b");
+        assert!(parse_panics("Part 1: 1\n").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_output() {
+        assert_eq!(
+            resolve_output(None, None),
+            (PathBuf::from("analytics.md"), OutputFormat::Markdown)
+        );
+        assert_eq!(
+            resolve_output(None, Some(OutputFormat::Json)),
+            (PathBuf::from("analytics.json"), OutputFormat::Json)
+        );
+        assert_eq!(
+            resolve_output(Some("out/report.HTML"), None),
+            (PathBuf::from("out/report.HTML"), OutputFormat::Html)
+        );
+        assert_eq!(
+            resolve_output(Some("report.txt"), None),
+            (PathBuf::from("report.txt"), OutputFormat::Markdown)
+        );
+        assert_eq!(
+            resolve_output(Some("report.txt"), Some(OutputFormat::Csv)),
+            (PathBuf::from("report.txt"), OutputFormat::Csv)
+        );
+    }
 }
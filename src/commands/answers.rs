@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::analytics::PartPanic;
+
+/// What `utils::NoPart` prints as the answer of a puzzle without a part 2.
+pub const NO_PART: &str = "—";
+
+/// Answers for both parts of a day, either recorded or produced by a run.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    /// Loads recorded answers from a file of `part1: <answer>` / `part2: <answer>`
+    /// lines. Blank lines and `#` comments are ignored.
+    ///
+    /// Returns `None` if the file does not exist.
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content =
+            fs::read_to_string(path).context(format!("Failed to read {}", path.display()))?;
+        let mut answers = Self::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line.split_once(':').context(format!(
+                "{}:{}: expected 'part1: <answer>' or 'part2: <answer>'",
+                path.display(),
+                index + 1
+            ))?;
+            let value = Some(value.trim().to_string()).filter(|value| !value.is_empty());
+
+            match key.trim() {
+                "part1" => answers.part1 = value,
+                "part2" => answers.part2 = value,
+                other => anyhow::bail!(
+                    "{}:{}: unknown key '{other}', expected 'part1' or 'part2'",
+                    path.display(),
+                    index + 1
+                ),
+            }
+        }
+
+        Ok(Some(answers))
+    }
+
+    /// Extracts the answers from the output of a solution run by `run_solution!`,
+    /// in either normal or timing mode.
+    pub fn from_output(output: &str) -> Self {
+        let mut answers = Self::default();
+
+        for line in output.lines() {
+            if let Some(answer) = line.strip_prefix("Part 1: ") {
+                answers.part1 = Some(strip_timing(answer).to_string());
+            } else if let Some(answer) = line.strip_prefix("Part 2: ") {
+                answers.part2 = Some(strip_timing(answer).to_string());
+            }
+        }
+
+        answers
+    }
+}
+
+/// Removes the ` (1.234ms)` suffix that timing mode appends to each answer.
+fn strip_timing(answer: &str) -> &str {
+    let answer = answer.trim();
+
+    answer
+        .strip_suffix("ms)")
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .filter(|(_, millis)| millis.parse::<f64>().is_ok())
+        .map_or(answer, |(answer, _)| answer)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail,
+    Missing,
+    /// The puzzle has no such part.
+    NoPart,
+}
+
+impl Check {
+    pub fn of(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (_, Some(NO_PART)) => Self::NoPart,
+            (None, _) => Self::Missing,
+            (Some(expected), Some(actual)) if expected == actual => Self::Pass,
+            (Some(_), _) => Self::Fail,
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "FAIL",
+            Self::Missing => "missing",
+            Self::NoPart => NO_PART,
+        }
+    }
+}
+
+/// How one part of a run turned out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartOutcome {
+    Checked(Check),
+    Panicked,
+    /// The run failed without an answer for this part, e.g. it returned an error.
+    Crashed,
+}
+
+impl PartOutcome {
+    /// A part that panicked, or that has no answer in a failed run, is not checked.
+    /// The other part of the run still is.
+    pub fn of(
+        part: u8,
+        expected: Option<&str>,
+        actual: Option<&str>,
+        panics: &[PartPanic],
+        failed: bool,
+    ) -> Self {
+        let stage = format!("part {part}");
+        if panics.iter().any(|panic| panic.stage == stage) {
+            Self::Panicked
+        } else if failed && actual.is_none() {
+            Self::Crashed
+        } else {
+            Self::Checked(Check::of(expected, actual))
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Checked(check) => check.label(),
+            Self::Panicked => "panicked",
+            Self::Crashed => "crashed",
+        }
+    }
+
+    pub fn passed(self) -> bool {
+        matches!(self, Self::Checked(check) if check != Check::Fail)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_of() {
+        assert_eq!(Check::of(Some("42"), Some("42")), Check::Pass);
+        assert_eq!(Check::of(Some("42"), Some("41")), Check::Fail);
+        assert_eq!(Check::of(Some("42"), None), Check::Fail);
+        assert_eq!(Check::of(None, Some("42")), Check::Missing);
+        assert_eq!(Check::of(None, None), Check::Missing);
+        assert_eq!(Check::of(Some("42"), Some(NO_PART)), Check::NoPart);
+        assert_eq!(Check::of(None, Some(NO_PART)), Check::NoPart);
+    }
+
+    #[test]
+    fn test_strip_timing() {
+        assert_eq!(strip_timing("42 (1.234ms)"), "42");
+        assert_eq!(strip_timing("  42 (0ms) "), "42");
+        assert_eq!(strip_timing("42"), "42");
+        assert_eq!(strip_timing("a (b) (2.5ms)"), "a (b)");
+        assert_eq!(strip_timing("EFHK (not ms)"), "EFHK (not ms)");
+        assert_eq!(strip_timing("(ms)"), "(ms)");
+    }
+
+    #[test]
+    fn test_answers_from_output() {
+        let output = "Part 1: 42 (1.5ms)\nPart 2: abc (0.1ms)\nTIMING:1500:100\n";
+        assert_eq!(
+            Answers::from_output(output),
+            Answers {
+                part1: Some("42".to_string()),
+                part2: Some("abc".to_string()),
+            }
+        );
+
+        let output = "Part 1: 42\nPANIC:part 2:boom\n";
+        assert_eq!(
+            Answers::from_output(output),
+            Answers {
+                part1: Some("42".to_string()),
+                part2: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_part_outcome_of() {
+        let panics = [PartPanic {
+            stage: "part 2".to_string(),
+            message: "boom".to_string(),
+        }];
+
+        assert_eq!(
+            PartOutcome::of(1, Some("1"), Some("1"), &panics, true),
+            PartOutcome::Checked(Check::Pass)
+        );
+        assert_eq!(
+            PartOutcome::of(2, Some("2"), None, &panics, true),
+            PartOutcome::Panicked
+        );
+        assert_eq!(
+            PartOutcome::of(2, Some("2"), None, &[], true),
+            PartOutcome::Crashed
+        );
+        assert_eq!(
+            PartOutcome::of(2, Some("2"), None, &[], false),
+            PartOutcome::Checked(Check::Fail)
+        );
+    }
+}
@@ -0,0 +1,397 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use super::analytics::parse_timing;
+use super::build;
+use super::config::{self, BuildProfile, BuildSettings};
+use super::process;
+use super::time::{detect_current_day, find_workspace_root};
+
+// z-score for a two-sided 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// A `git worktree` checked out at the baseline revision, removed on drop.
+///
+/// It lives at `target/aoc-bench/<commit>` in the workspace, so one left behind by an
+/// interrupted run is found and replaced by the next.
+struct Worktree {
+    repo_root: PathBuf,
+    path: PathBuf,
+}
+
+impl Worktree {
+    fn add(repo_root: &Path, workspace_root: &Path, revision: &str) -> Result<Self> {
+        let commit = git_output(
+            workspace_root,
+            &["rev-parse", "--verify", &format!("{revision}^{{commit}}")],
+        )?;
+        let path = workspace_root.join("target/aoc-bench").join(commit);
+
+        if path.exists() {
+            remove_worktree(repo_root, &path);
+            if path.exists() {
+                fs::remove_dir_all(&path).context(format!(
+                    "Failed to remove the old worktree at {}",
+                    path.display()
+                ))?;
+            }
+            let _ = Command::new("git")
+                .current_dir(repo_root)
+                .args(["worktree", "prune"])
+                .output();
+        }
+
+        let output = Command::new("git")
+            .current_dir(repo_root)
+            .args(["worktree", "add", "--detach"])
+            .arg(&path)
+            .arg(revision)
+            .output()
+            .context("Failed to run git worktree")?;
+
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to check out '{revision}':\n{}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            path,
+        })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        remove_worktree(&self.repo_root, &self.path);
+    }
+}
+
+fn remove_worktree(repo_root: &Path, path: &Path) {
+    let _ = Command::new("git")
+        .current_dir(repo_root)
+        .args(["worktree", "remove", "--force"])
+        .arg(path)
+        .output();
+}
+
+/// Timing samples of one part, in microseconds.
+#[derive(Default)]
+struct Samples(Vec<f64>);
+
+impl Samples {
+    #[allow(clippy::cast_precision_loss)]
+    fn push(&mut self, micros: u128) {
+        self.0.push(micros as f64);
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn mean(&self) -> f64 {
+        self.0.iter().sum::<f64>() / self.0.len() as f64
+    }
+
+    /// Standard error of the mean.
+    #[allow(clippy::cast_precision_loss)]
+    fn standard_error(&self) -> f64 {
+        let n = self.0.len() as f64;
+        if n < 2.0 {
+            return 0.0;
+        }
+
+        let mean = self.mean();
+        let variance = self.0.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (variance / n).sqrt()
+    }
+}
+
+/// One side of the comparison: a built day binary and the directory it runs in.
+struct Side {
+    name: String,
+    binary: PathBuf,
+    day_dir: PathBuf,
+    samples: [Samples; 2],
+    /// Runs that failed, timed out or did not report their timing.
+    failures: usize,
+}
+
+impl Side {
+    fn new(name: String, binary: &Path, day_dir: PathBuf) -> Self {
+        Self {
+            name,
+            binary: binary.to_path_buf(),
+            day_dir,
+            samples: Default::default(),
+            failures: 0,
+        }
+    }
+
+    /// Runs the day once and records the time of each part that finished. A failed
+    /// run is counted, and its first error shown, instead of stopping the benchmark.
+    fn run(&mut self, day: u8, timeout: Duration) -> Result<()> {
+        let output = process::run_measured(
+            Command::new(&self.binary)
+                .arg("--timing")
+                .current_dir(&self.day_dir),
+            Some(timeout),
+        )
+        .context(format!("Failed to run {}", self.binary.display()))?;
+
+        let timing = parse_timing(&output.stdout, day, None);
+
+        if !output.success() || timing.is_none() {
+            if self.failures == 0 {
+                eprintln!(
+                    "Warning: day {day} failed at {}: {}",
+                    self.name,
+                    output.error_message()
+                );
+            }
+            self.failures += 1;
+        }
+
+        let Some(timing) = timing else {
+            return Ok(());
+        };
+
+        for (part, micros) in [timing.part1_micros, timing.part2_micros]
+            .into_iter()
+            .enumerate()
+        {
+            if let Some(micros) = micros {
+                self.samples[part].push(micros);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct DayBench {
+    day: u8,
+    baseline: Side,
+    current: Side,
+}
+
+/// Benchmarks the requested days of the current tree against a baseline revision.
+///
+/// The baseline is checked out into a git worktree under `target/aoc-bench`, both
+/// trees are built with the workspace's build profile, and the runs are interleaved
+/// so that drift in machine load affects both sides equally. A run that fails or
+/// takes longer than `timeout` is counted as failed.
+pub fn bench_against(
+    baseline: &str,
+    days: &[u8],
+    iterations: usize,
+    timeout: Duration,
+) -> Result<()> {
+    if iterations == 0 {
+        anyhow::bail!("Need at least one iteration");
+    }
+
+    let workspace_root = find_workspace_root()?;
+    let days = resolve_days(&workspace_root, days)?;
+    let settings = BuildSettings::load(&workspace_root)?;
+
+    let repo_root = git_output(&workspace_root, &["rev-parse", "--show-toplevel"])?;
+    let prefix = git_output(&workspace_root, &["rev-parse", "--show-prefix"])?;
+
+    println!("Checking out {baseline} into a worktree...");
+
+    let worktree = Worktree::add(Path::new(&repo_root), &workspace_root, baseline)?;
+    let baseline_root = worktree.path.join(prefix);
+
+    prepare_baseline(&workspace_root, &baseline_root, &days, settings)?;
+
+    println!("\nBuilding current tree ({settings})...\n");
+    let current_build = build::build_workspace(&workspace_root, settings)?;
+
+    println!("\nBuilding {baseline} ({settings})...\n");
+    let baseline_build = build::build_workspace(&baseline_root, settings)?;
+
+    let mut runnable = Vec::new();
+    for day in days {
+        let day_name = format!("day{day:02}");
+
+        match (
+            current_build.binary(&day_name),
+            baseline_build.binary(&day_name),
+        ) {
+            (Some(current), Some(baseline_binary)) => runnable.push(DayBench {
+                day,
+                baseline: Side::new(
+                    baseline.to_string(),
+                    baseline_binary,
+                    baseline_root.join(&day_name),
+                ),
+                current: Side::new(
+                    "the current tree".to_string(),
+                    current,
+                    workspace_root.join(&day_name),
+                ),
+            }),
+            (None, _) => eprintln!("Warning: {day_name} failed to build in the current tree"),
+            (_, None) => eprintln!("Warning: {day_name} failed to build at {baseline}"),
+        }
+    }
+
+    if runnable.is_empty() {
+        anyhow::bail!("No day could be built in both trees");
+    }
+
+    println!("\nRunning {iterations} interleaved iteration(s)...\n");
+
+    for iteration in 0..iterations {
+        for bench in &mut runnable {
+            // Alternate which side goes first so neither always gets the warm cache
+            if iteration % 2 == 0 {
+                bench.current.run(bench.day, timeout)?;
+                bench.baseline.run(bench.day, timeout)?;
+            } else {
+                bench.baseline.run(bench.day, timeout)?;
+                bench.current.run(bench.day, timeout)?;
+            }
+        }
+    }
+
+    println!("| day | part | baseline   | current    | change         | 95% CI          |");
+    println!("| --- | ---- | ---------- | ---------- | -------------- | --------------- |");
+
+    for bench in &runnable {
+        for part in 0..2 {
+            print_comparison(
+                bench.day,
+                part + 1,
+                &bench.baseline.samples[part],
+                &bench.current.samples[part],
+            );
+        }
+    }
+
+    for bench in &runnable {
+        for side in [&bench.baseline, &bench.current] {
+            if side.failures > 0 {
+                println!(
+                    "\nDay {}: {} of {iterations} run(s) failed at {}",
+                    bench.day, side.failures, side.name
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn resolve_days(workspace_root: &Path, days: &[u8]) -> Result<Vec<u8>> {
+    if let Some(day) = days.iter().find(|day| !(1..=25).contains(*day)) {
+        anyhow::bail!("Day must be between 1 and 25, got {day}");
+    }
+
+    let days = if days.is_empty() {
+        match detect_current_day() {
+            Ok(day) => vec![day],
+            Err(_) => (1..=25)
+                .filter(|day| workspace_root.join(format!("day{day:02}")).exists())
+                .collect(),
+        }
+    } else {
+        days.to_vec()
+    };
+
+    if days.is_empty() {
+        anyhow::bail!("No day crates found");
+    }
+
+    Ok(days)
+}
+
+/// Makes the baseline tree comparable: same inputs (usually not committed) and the
+/// same build profile definition.
+fn prepare_baseline(
+    workspace_root: &Path,
+    baseline_root: &Path,
+    days: &[u8],
+    settings: BuildSettings,
+) -> Result<()> {
+    for day in days {
+        let day_name = format!("day{day:02}");
+        let input = workspace_root.join(&day_name).join("input.txt");
+        let baseline_day = baseline_root.join(&day_name);
+
+        if baseline_day.exists() && input.exists() {
+            fs::copy(&input, baseline_day.join("input.txt")).context(format!(
+                "Failed to copy {day_name}/input.txt to the baseline"
+            ))?;
+        }
+    }
+
+    if settings.profile == BuildProfile::Bench {
+        config::ensure_bench_profile(baseline_root)?;
+    }
+
+    Ok(())
+}
+
+fn print_comparison(day: u8, part: usize, baseline: &Samples, current: &Samples) {
+    if baseline.0.is_empty() || current.0.is_empty() {
+        return;
+    }
+
+    let (baseline_mean, current_mean) = (baseline.mean(), current.mean());
+
+    if baseline_mean <= 0.0 || current_mean <= 0.0 {
+        println!(
+            "| {day:>3} | {part:>4} | {:>8.3}ms | {:>8.3}ms | {:<14} | {:<15} |",
+            baseline_mean / 1000.0,
+            current_mean / 1000.0,
+            "too fast",
+            "-"
+        );
+        return;
+    }
+
+    // Speedup ratio with a log-normal interval from the delta method
+    let speedup = baseline_mean / current_mean;
+    let relative_error = ((baseline.standard_error() / baseline_mean).powi(2)
+        + (current.standard_error() / current_mean).powi(2))
+    .sqrt();
+    let low = speedup * (-Z_95 * relative_error).exp();
+    let high = speedup * (Z_95 * relative_error).exp();
+
+    let change = if low > 1.0 {
+        format!("{speedup:.2}x faster")
+    } else if high < 1.0 {
+        format!("{:.2}x slower", 1.0 / speedup)
+    } else {
+        "no change".to_string()
+    };
+
+    println!(
+        "| {day:>3} | {part:>4} | {:>8.3}ms | {:>8.3}ms | {change:<14} | {:<15} |",
+        baseline_mean / 1000.0,
+        current_mean / 1000.0,
+        format!("{low:.2}x - {high:.2}x"),
+    );
+}
+
+fn git_output(directory: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(directory)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed. Is the workspace in a git repository?\n{}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
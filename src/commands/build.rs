@@ -0,0 +1,219 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::config::BuildSettings;
+
+/// Result of a single `cargo build` invocation over the workspace.
+#[derive(Debug, Default)]
+pub struct BuildOutput {
+    /// Executable path of every binary target that was built, keyed by target name.
+    pub binaries: BTreeMap<String, PathBuf>,
+    /// Rendered compiler errors, keyed by the name of the target that failed.
+    pub errors: BTreeMap<String, Vec<String>>,
+}
+
+impl BuildOutput {
+    pub fn binary(&self, name: &str) -> Option<&Path> {
+        self.binaries.get(name).map(PathBuf::as_path)
+    }
+
+    pub fn errors_for(&self, name: &str) -> Option<&[String]> {
+        self.errors.get(name).map(Vec::as_slice)
+    }
+
+    /// Takes one line of `cargo build --message-format=json` output.
+    fn record(&mut self, line: &str) {
+        // Build scripts may print non-JSON lines; they are not ours to interpret
+        let Ok(message) = serde_json::from_str::<CargoMessage>(line) else {
+            return;
+        };
+
+        match message {
+            CargoMessage::CompilerArtifact {
+                target,
+                executable: Some(executable),
+            } => {
+                self.binaries.insert(target.name, executable);
+            }
+            CargoMessage::CompilerMessage { target, message } if message.level == "error" => {
+                if let Some(rendered) = message.rendered {
+                    self.errors.entry(target.name).or_default().push(rendered);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum CargoMessage {
+    CompilerArtifact {
+        target: Target,
+        executable: Option<PathBuf>,
+    },
+    CompilerMessage {
+        target: Target,
+        message: Diagnostic,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct Target {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Diagnostic {
+    level: String,
+    rendered: Option<String>,
+}
+
+/// Builds the whole workspace once with the configured profile and collects the
+/// binary paths and compile errors from cargo's JSON messages.
+///
+/// Crates that fail to compile do not stop the build (`--keep-going`): their errors
+/// are recorded and the remaining binaries are still produced.
+pub fn build_workspace(workspace_root: &Path, settings: BuildSettings) -> Result<BuildOutput> {
+    run_cargo_build(workspace_root, settings, &["--workspace", "--keep-going"])
+}
+
+/// Builds a single package of the workspace with the configured profile.
+pub fn build_package(
+    workspace_root: &Path,
+    settings: BuildSettings,
+    package: &str,
+) -> Result<BuildOutput> {
+    run_cargo_build(workspace_root, settings, &["-p", package])
+}
+
+fn run_cargo_build(
+    workspace_root: &Path,
+    settings: BuildSettings,
+    scope: &[&str],
+) -> Result<BuildOutput> {
+    let mut command = Command::new("cargo");
+    command
+        .current_dir(workspace_root)
+        .args(["build", "--message-format=json"])
+        .args(settings.profile.cargo_args())
+        .args(scope);
+
+    if let Some(rustflags) = settings.rustflags() {
+        let existing = std::env::var("RUSTFLAGS").unwrap_or_default();
+        command.env("RUSTFLAGS", format!("{existing} {rustflags}").trim());
+    }
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run cargo build")?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .context("Failed to capture cargo output")?;
+    let stderr = child
+        .stderr
+        .take()
+        .context("Failed to capture cargo output")?;
+
+    // Cargo's progress still goes to the terminal; anything else it says (a broken
+    // manifest, an unknown package, ...) is kept for the failure message
+    let stderr_reader = std::thread::spawn(move || {
+        let mut messages = Vec::new();
+        for line in std::io::BufReader::new(stderr)
+            .lines()
+            .map_while(Result::ok)
+        {
+            if is_cargo_status(&line) {
+                eprintln!("{line}");
+            } else {
+                messages.push(line);
+            }
+        }
+        messages
+    });
+    let mut output = BuildOutput::default();
+
+    for line in std::io::BufReader::new(stdout).lines() {
+        let line = line.context("Failed to read cargo output")?;
+        output.record(&line);
+    }
+
+    let status = child.wait().context("Failed to wait for cargo build")?;
+    let messages = stderr_reader.join().unwrap_or_default().join("\n");
+
+    // Compile errors are reported per crate by the callers; any other failure would
+    // otherwise look like a missing binary
+    if !status.success() && output.errors.is_empty() {
+        anyhow::bail!("cargo build failed ({status})\n{messages}");
+    }
+    if !messages.is_empty() {
+        eprintln!("{messages}");
+    }
+
+    Ok(output)
+}
+
+/// Whether `line` is one of cargo's right-aligned progress lines, e.g.
+/// `   Compiling day01 v0.1.0`.
+fn is_cargo_status(line: &str) -> bool {
+    line.starts_with(' ')
+        && line.split_whitespace().next().is_some_and(|word| {
+            word.starts_with(char::is_uppercase) && word.chars().all(char::is_alphabetic)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_cargo_messages() {
+        let mut output = BuildOutput::default();
+        for line in [
+            r#"{"reason":"compiler-artifact","target":{"name":"utils","kind":["lib"]},"executable":null}"#,
+            r#"{"reason":"compiler-artifact","target":{"name":"day01","kind":["bin"]},"executable":"/ws/target/release/day01"}"#,
+            r#"{"reason":"compiler-message","target":{"name":"day02"},"message":{"level":"warning","rendered":"warning: unused variable"}}"#,
+            r#"{"reason":"compiler-message","target":{"name":"day02"},"message":{"level":"error","rendered":"error[E0308]: mismatched types"}}"#,
+            r#"{"reason":"compiler-message","target":{"name":"day02"},"message":{"level":"error","rendered":null}}"#,
+            r#"{"reason":"build-script-executed","package_id":"utils"}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+            "not json from a build script",
+        ] {
+            output.record(line);
+        }
+
+        assert_eq!(
+            output.binary("day01"),
+            Some(Path::new("/ws/target/release/day01"))
+        );
+        assert_eq!(output.binary("utils"), None);
+        assert_eq!(
+            output.errors_for("day02"),
+            Some(&["error[E0308]: mismatched types".to_string()][..])
+        );
+        assert_eq!(output.errors_for("day01"), None);
+    }
+
+    #[test]
+    fn test_is_cargo_status() {
+        assert!(is_cargo_status("   Compiling day01 v0.1.0 (/ws/day01)"));
+        assert!(is_cargo_status(
+            "    Finished `release` profile [optimized] target(s)"
+        ));
+        assert!(!is_cargo_status(
+            "error: package ID specification `day99` did not match"
+        ));
+        assert!(!is_cargo_status("  --> day01/src/lib.rs:3:5"));
+        assert!(!is_cargo_status(""));
+    }
+}
@@ -0,0 +1,215 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use super::time::find_workspace_root;
+
+const CONFIG_FILE: &str = ".aoc/config.json";
+
+const BENCH_PROFILE_NAME: &str = "aoc-bench";
+// Panics must unwind so that `run_solution!` can catch them per part
+const BENCH_PROFILE: &str = r#"
+# Used by `aoc-cli profile bench` for timing runs
+[profile.aoc-bench]
+inherits = "release"
+lto = "fat"
+codegen-units = 1
+"#;
+// Written by earlier versions, under which a panic ended the whole run
+const ABORT_ON_PANIC: &str = "panic = \"abort\"";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BuildProfile {
+    Debug,
+    #[default]
+    Release,
+    Bench,
+}
+
+impl BuildProfile {
+    /// Arguments selecting this profile on the cargo command line.
+    pub const fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Self::Debug => &[],
+            Self::Release => &["--release"],
+            Self::Bench => &["--profile", BENCH_PROFILE_NAME],
+        }
+    }
+}
+
+impl fmt::Display for BuildProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Debug => write!(f, "debug"),
+            Self::Release => write!(f, "release"),
+            Self::Bench => write!(f, "bench"),
+        }
+    }
+}
+
+/// Workspace-level build options used by every timing run, stored in `.aoc/config.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildSettings {
+    #[serde(default)]
+    pub profile: BuildProfile,
+    #[serde(default)]
+    pub target_cpu_native: bool,
+}
+
+impl BuildSettings {
+    pub fn load(workspace_root: &Path) -> Result<Self> {
+        let path = workspace_root.join(CONFIG_FILE);
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).context(format!("Failed to read {CONFIG_FILE}"))?;
+
+        serde_json::from_str(&content).context(format!("Invalid {CONFIG_FILE}"))
+    }
+
+    fn save(self, workspace_root: &Path) -> Result<()> {
+        let path = workspace_root.join(CONFIG_FILE);
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).context("Failed to create .aoc directory")?;
+        }
+
+        let content =
+            serde_json::to_string_pretty(&self).context("Failed to serialize build settings")?;
+        fs::write(&path, content + "\n").context(format!("Failed to write {CONFIG_FILE}"))
+    }
+
+    /// Extra `RUSTFLAGS` needed on top of the profile, if any.
+    pub const fn rustflags(self) -> Option<&'static str> {
+        if self.target_cpu_native {
+            Some("-C target-cpu=native")
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for BuildSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.profile)?;
+
+        if self.target_cpu_native {
+            write!(f, " + target-cpu=native")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Shows or changes the build profile used by `time`, `analytics` and the other
+/// commands that build day crates.
+pub fn configure_profile(profile: Option<BuildProfile>, native: Option<bool>) -> Result<()> {
+    let workspace_root = find_workspace_root()?;
+    let mut settings = BuildSettings::load(&workspace_root)?;
+
+    if profile.is_none() && native.is_none() {
+        println!("Build profile: {settings}");
+        return Ok(());
+    }
+
+    if let Some(profile) = profile {
+        settings.profile = profile;
+    }
+
+    if let Some(native) = native {
+        settings.target_cpu_native = native;
+    }
+
+    if settings.profile == BuildProfile::Bench {
+        ensure_bench_profile(&workspace_root)?;
+    }
+
+    settings.save(&workspace_root)?;
+
+    println!("Build profile set to: {settings}");
+
+    Ok(())
+}
+
+/// Adds the `[profile.aoc-bench]` section to the workspace manifest if it is missing,
+/// for workspaces created before it was part of the template, and removes the
+/// `panic = "abort"` that earlier versions generated in it.
+pub(super) fn ensure_bench_profile(workspace_root: &Path) -> Result<()> {
+    let cargo_toml = workspace_root.join("Cargo.toml");
+    let content = fs::read_to_string(&cargo_toml).context("Failed to read Cargo.toml")?;
+
+    let Some(updated) = update_bench_profile(&content) else {
+        return Ok(());
+    };
+
+    fs::write(&cargo_toml, updated).context("Failed to write Cargo.toml")?;
+
+    println!("Updated [profile.{BENCH_PROFILE_NAME}] in Cargo.toml");
+
+    Ok(())
+}
+
+/// The manifest with an up-to-date bench profile, or `None` if it already has one.
+fn update_bench_profile(content: &str) -> Option<String> {
+    let header = format!("[profile.{BENCH_PROFILE_NAME}]");
+
+    let Some(start) = content.find(&header) else {
+        return Some(content.trim_end().to_string() + "\n" + BENCH_PROFILE);
+    };
+
+    // The profile's section ends where the next one begins
+    let section_end = content[start + header.len()..]
+        .find("\n[")
+        .map_or(content.len(), |end| start + header.len() + end + 1);
+    let section = &content[start..section_end];
+
+    let kept: Vec<&str> = section
+        .split_inclusive('\n')
+        .filter(|line| line.trim() != ABORT_ON_PANIC)
+        .collect();
+    if kept.len() == section.split_inclusive('\n').count() {
+        return None;
+    }
+
+    Some(format!(
+        "{}{}{}",
+        &content[..start],
+        kept.concat(),
+        &content[section_end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_bench_profile() {
+        let workspace = "[workspace]\nmembers = [\"day*\"]\n";
+        assert_eq!(
+            update_bench_profile(workspace),
+            Some(format!("{workspace}{BENCH_PROFILE}"))
+        );
+
+        let current = format!("{workspace}{BENCH_PROFILE}");
+        assert_eq!(update_bench_profile(&current), None);
+
+        let aborting = format!(
+            "{workspace}\n[profile.aoc-bench]\ninherits = \"release\"\npanic = \"abort\"\n\n\
+             [profile.release]\npanic = \"abort\"\n"
+        );
+        assert_eq!(
+            update_bench_profile(&aborting),
+            Some(format!(
+                "{workspace}\n[profile.aoc-bench]\ninherits = \"release\"\n\n\
+                 [profile.release]\npanic = \"abort\"\n"
+            ))
+        );
+    }
+}
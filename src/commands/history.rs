@@ -0,0 +1,362 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+use std::path::Path;
+use std::process::Command;
+
+use super::analytics::{DayStatus, DayTiming, micros_to_ms};
+use super::config::BuildSettings;
+use super::time::find_workspace_root;
+
+const HISTORY_DIR: &str = ".aoc";
+const HISTORY_FILE: &str = ".aoc/timings.jsonl";
+
+/// One analytics run, stored as a single line of `.aoc/timings.jsonl`.
+#[derive(Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: String,
+    pub commit: Option<String>,
+    pub rustc: Option<String>,
+    pub machine: String,
+    #[serde(default)]
+    pub build: Option<BuildSettings>,
+    pub days: Vec<DayTiming>,
+}
+
+impl RunRecord {
+    fn day(&self, day: u8) -> Option<&DayTiming> {
+        self.days.iter().find(|timing| timing.day == day)
+    }
+
+    fn label(&self) -> String {
+        let timestamp = chrono::DateTime::parse_from_rfc3339(&self.timestamp).map_or_else(
+            |_| self.timestamp.clone(),
+            |time| time.format("%Y-%m-%d %H:%M").to_string(),
+        );
+
+        format!(
+            "{timestamp}  {}  {}  {}",
+            self.commit.as_deref().unwrap_or("no commit"),
+            self.machine,
+            self.build
+                .map_or_else(|| "unknown profile".to_string(), |build| build.to_string())
+        )
+    }
+}
+
+/// Appends the results of an analytics run to the timing history. `commit` is
+/// taken with [`current_commit`] before the run writes any files.
+pub fn record_run(
+    workspace_root: &Path,
+    timings: &[DayTiming],
+    settings: BuildSettings,
+    commit: Option<String>,
+) -> Result<()> {
+    let record = RunRecord {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        commit,
+        rustc: rustc_version(),
+        machine: machine_id(),
+        build: Some(settings),
+        days: timings.to_vec(),
+    };
+
+    fs::create_dir_all(workspace_root.join(HISTORY_DIR))
+        .context(format!("Failed to create {HISTORY_DIR}"))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(workspace_root.join(HISTORY_FILE))
+        .context(format!("Failed to open {HISTORY_FILE}"))?;
+
+    let line = serde_json::to_string(&record).context("Failed to serialize timing record")?;
+    writeln!(file, "{line}").context(format!("Failed to write {HISTORY_FILE}"))?;
+
+    Ok(())
+}
+
+fn load_runs() -> Result<Vec<RunRecord>> {
+    let path = find_workspace_root()?.join(HISTORY_FILE);
+
+    if !path.exists() {
+        anyhow::bail!("No timing history found. Run 'aoc-cli analytics' first.");
+    }
+
+    let content = fs::read_to_string(&path).context(format!("Failed to read {HISTORY_FILE}"))?;
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).context(format!(
+                "Invalid record on line {} of {HISTORY_FILE}",
+                index + 1
+            ))
+        })
+        .collect()
+}
+
+/// The short hash of `HEAD`, suffixed with `-dirty` if tracked files have changes.
+/// Untracked files (inputs, reports, the history itself) do not count.
+pub fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|status| !status.stdout.is_empty());
+
+    Some(if dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+fn rustc_version() -> Option<String> {
+    let output = Command::new("rustc").arg("--version").output().ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn machine_id() -> String {
+    ["/etc/machine-id", "/var/lib/dbus/machine-id"]
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok())
+        .map(|id| id.trim().chars().take(12).collect::<String>())
+        .filter(|id| !id.is_empty())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Prints each day's total time over the last `last` runs, oldest first.
+pub fn show_history(day: Option<u8>, last: usize) -> Result<()> {
+    let runs = load_runs()?;
+    let first_index = runs.len().saturating_sub(last.max(1));
+    let runs = &runs[first_index..];
+
+    println!("Runs:");
+    for (offset, run) in runs.iter().enumerate() {
+        println!("  #{:<3} {}", first_index + offset + 1, run.label());
+    }
+    println!();
+
+    let days: BTreeSet<u8> = runs
+        .iter()
+        .flat_map(|run| run.days.iter().map(|timing| timing.day))
+        .filter(|d| day.is_none_or(|wanted| wanted == *d))
+        .collect();
+
+    if days.is_empty() {
+        anyhow::bail!("No recorded timings for the requested day");
+    }
+
+    let mut header = String::from("       |");
+    for number in first_index + 1..=first_index + runs.len() {
+        let _ = write!(header, " {:>11}", format!("#{number}"));
+    }
+    println!("{header}");
+
+    for day in days {
+        let mut line = format!("day {day:>2} |");
+
+        for run in runs {
+            match run.day(day) {
+                Some(DayTiming {
+                    status: DayStatus::Timeout,
+                    ..
+                }) => line.push_str("     timeout"),
+                Some(DayTiming {
+                    status: DayStatus::Failed,
+                    ..
+                }) => line.push_str("      failed"),
+                Some(DayTiming {
+                    status: DayStatus::Panicked,
+                    ..
+                }) => line.push_str("    panicked"),
+                Some(timing) => match timing.total_ms() {
+                    Some(total_ms) => {
+                        let _ = write!(line, " {total_ms:>9.3}ms");
+                    }
+                    None => line.push_str("           -"),
+                },
+                None => line.push_str("           -"),
+            }
+        }
+
+        let recorded: Vec<&DayTiming> = runs.iter().filter_map(|run| run.day(day)).collect();
+        if let [.., previous, latest] = recorded.as_slice()
+            && let (Some(before), Some(after)) = (previous.total_micros(), latest.total_micros())
+            && let Some(change) = percent_change(before, after)
+        {
+            let _ = write!(line, " | {change:+.1}%");
+        }
+
+        println!("{line}");
+    }
+
+    Ok(())
+}
+
+/// Compares the latest run with the previous one (or the chosen baseline) and flags
+/// every part that got slower by more than `threshold` percent.
+///
+/// The baseline is either a run number as shown by `history`, or a commit prefix,
+/// in which case the latest run recorded at that commit is used.
+pub fn compare_runs(baseline: Option<&str>, threshold: f64) -> Result<()> {
+    let runs = load_runs()?;
+
+    let Some((latest, earlier)) = runs.split_last() else {
+        anyhow::bail!("No timing history found. Run 'aoc-cli analytics' first.");
+    };
+
+    let baseline = match baseline {
+        None => earlier
+            .last()
+            .context("Only one run recorded, nothing to compare against")?,
+        Some(selector) => find_baseline(earlier, selector)?,
+    };
+
+    println!("Baseline: {}", baseline.label());
+    println!("Current:  {}\n", latest.label());
+
+    if baseline.build != latest.build {
+        println!("Note: runs used different build profiles, timings are not like-for-like\n");
+    }
+
+    if baseline.rustc != latest.rustc || baseline.machine != latest.machine {
+        println!("Note: runs differ in rustc version or machine, timings may not be comparable\n");
+    }
+
+    println!("| day | part | baseline   | current    | change   |        |");
+    println!("| --- | ---- | ---------- | ---------- | -------- | ------ |");
+
+    let mut regressions = 0;
+
+    for current in &latest.days {
+        let Some(previous) = baseline.day(current.day) else {
+            continue;
+        };
+
+        for (part, before, after) in [
+            (1, previous.part1_micros, current.part1_micros),
+            (2, previous.part2_micros, current.part2_micros),
+        ] {
+            let (Some(before), Some(after)) = (before, after) else {
+                continue;
+            };
+            let change = percent_change(before, after);
+            let flag = match change {
+                Some(change) if change > threshold => {
+                    regressions += 1;
+                    "SLOWER"
+                }
+                Some(change) if change < -threshold => "faster",
+                _ => "",
+            };
+
+            println!(
+                "| {:>3} | {part:>4} | {:>8.3}ms | {:>8.3}ms | {:>8} | {flag:<6} |",
+                current.day,
+                micros_to_ms(before),
+                micros_to_ms(after),
+                change.map_or_else(|| "n/a".to_string(), |change| format!("{change:+.1}%")),
+            );
+        }
+    }
+
+    if regressions > 0 {
+        anyhow::bail!("{regressions} part(s) regressed by more than {threshold}%");
+    }
+
+    println!("\nNo regressions beyond {threshold}%");
+
+    Ok(())
+}
+
+/// A number is a run number if there is such a run; anything else, including an
+/// all-digit short hash such as `1234567`, is looked up as a commit prefix.
+fn find_baseline<'a>(runs: &'a [RunRecord], selector: &str) -> Result<&'a RunRecord> {
+    let by_number = selector
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|index| runs.get(index));
+
+    if let Some(run) = by_number {
+        return Ok(run);
+    }
+
+    runs.iter()
+        .rev()
+        .find(|run| {
+            run.commit
+                .as_deref()
+                .is_some_and(|commit| commit.starts_with(selector))
+        })
+        .context(format!(
+            "No earlier run #{selector} or recorded run for commit '{selector}'"
+        ))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn percent_change(before: u128, after: u128) -> Option<f64> {
+    (before > 0).then(|| (after as f64 - before as f64) / before as f64 * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(commit: &str) -> RunRecord {
+        RunRecord {
+            timestamp: String::new(),
+            commit: Some(commit.to_string()),
+            rustc: None,
+            machine: "test".to_string(),
+            build: None,
+            days: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_find_baseline() {
+        let runs = [run("abc1234"), run("1234567"), run("abc1234-dirty")];
+        let commit = |selector| {
+            find_baseline(&runs, selector)
+                .ok()
+                .and_then(|run| run.commit.clone())
+        };
+
+        // Run numbers are 1-based, as shown by `history`
+        assert_eq!(commit("1").as_deref(), Some("abc1234"));
+        assert_eq!(commit("3").as_deref(), Some("abc1234-dirty"));
+
+        // The latest run at a commit wins; all-digit hashes are commits too
+        assert_eq!(commit("abc").as_deref(), Some("abc1234-dirty"));
+        assert_eq!(commit("1234567").as_deref(), Some("1234567"));
+        assert_eq!(commit("12345").as_deref(), Some("1234567"));
+
+        assert!(find_baseline(&runs, "0").is_err());
+        assert!(find_baseline(&runs, "99").is_err());
+        assert!(find_baseline(&runs, "fff").is_err());
+    }
+}
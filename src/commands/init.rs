@@ -4,6 +4,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::process::Command;
 
+use super::runner;
 use crate::templates;
 
 fn extract_year_from_name(name: &str) -> String {
@@ -74,6 +75,56 @@ pub fn init_project(name: &str) -> Result<()> {
     )
     .context("Failed to write utils point3d.rs")?;
 
+    fs::write(utils_src_path.join("spans.rs"), templates::UTILS_SPANS_RS)
+        .context("Failed to write utils spans.rs")?;
+
+    fs::write(utils_src_path.join("error.rs"), templates::UTILS_ERROR_RS)
+        .context("Failed to write utils error.rs")?;
+
+    fs::write(utils_src_path.join("timer.rs"), templates::UTILS_TIMER_RS)
+        .context("Failed to write utils timer.rs")?;
+
+    fs::write(utils_src_path.join("params.rs"), templates::UTILS_PARAMS_RS)
+        .context("Failed to write utils params.rs")?;
+
+    fs::write(
+        utils_src_path.join("testing.rs"),
+        templates::UTILS_TESTING_RS,
+    )
+    .context("Failed to write utils testing.rs")?;
+
+    fs::write(
+        utils_src_path.join("visualize.rs"),
+        templates::UTILS_VISUALIZE_RS,
+    )
+    .context("Failed to write utils visualize.rs")?;
+
+    fs::write(
+        utils_src_path.join("registry.rs"),
+        templates::UTILS_REGISTRY_RS,
+    )
+    .context("Failed to write utils registry.rs")?;
+
+    fs::write(utils_src_path.join("debug.rs"), templates::UTILS_DEBUG_RS)
+        .context("Failed to write utils debug.rs")?;
+
+    fs::write(utils_src_path.join("panics.rs"), templates::UTILS_PANICS_RS)
+        .context("Failed to write utils panics.rs")?;
+
+    fs::write(utils_src_path.join("grid.rs"), templates::UTILS_GRID_RS)
+        .context("Failed to write utils grid.rs")?;
+
+    fs::write(
+        utils_src_path.join("sparse_grid.rs"),
+        templates::UTILS_SPARSE_GRID_RS,
+    )
+    .context("Failed to write utils sparse_grid.rs")?;
+
+    fs::write(utils_src_path.join("hex.rs"), templates::UTILS_HEX_RS)
+        .context("Failed to write utils hex.rs")?;
+
+    runner::sync_runner(&project_path)?;
+
     // Initialize git repository
     Command::new("git")
         .arg("init")
@@ -86,13 +137,29 @@ pub fn init_project(name: &str) -> Result<()> {
     println!("  {name}/");
     println!("  ├── Cargo.toml");
     println!("  ├── README.md");
+    println!("  ├── runner/");
+    println!("  │   ├── Cargo.toml");
+    println!("  │   └── src/");
+    println!("  │       └── main.rs");
     println!("  └── utils/");
     println!("      ├── Cargo.toml");
     println!("      └── src/");
     println!("          ├── lib.rs");
+    println!("          ├── debug.rs");
     println!("          ├── directions.rs");
+    println!("          ├── error.rs");
+    println!("          ├── grid.rs");
+    println!("          ├── hex.rs");
+    println!("          ├── panics.rs");
+    println!("          ├── params.rs");
     println!("          ├── point3d.rs");
-    println!("          └── points.rs");
+    println!("          ├── points.rs");
+    println!("          ├── registry.rs");
+    println!("          ├── spans.rs");
+    println!("          ├── sparse_grid.rs");
+    println!("          ├── testing.rs");
+    println!("          ├── timer.rs");
+    println!("          └── visualize.rs");
 
     Ok(())
 }
@@ -1,11 +1,36 @@
 mod add;
 mod analytics;
+mod answers;
+mod bench;
+mod build;
+mod config;
+mod history;
 mod init;
+mod process;
+mod remove;
+mod report;
+mod run;
+mod runner;
+mod spans;
+mod summary;
 mod time;
 mod update;
+mod verify;
+mod visualize;
+mod watch;
 
 pub use add::add_day;
 pub use analytics::run_analytics;
+pub use bench::bench_against;
+pub use config::{BuildProfile, configure_profile};
+pub use history::{compare_runs, show_history};
 pub use init::init_project;
+pub use remove::remove_day;
+pub use report::OutputFormat;
+pub use run::{run_all, run_day};
+pub use summary::BudgetLimit;
 pub use time::{TimePart, time_day};
 pub use update::update_utils;
+pub use verify::verify_answers;
+pub use visualize::visualize_day;
+pub use watch::watch_day;
@@ -0,0 +1,189 @@
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Resource usage of a finished child process.
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceUsage {
+    pub peak_rss_kb: u64,
+    pub cpu_micros: u128,
+}
+
+#[derive(Debug)]
+pub enum RunStatus {
+    Exited(ExitStatus),
+    TimedOut,
+}
+
+#[derive(Debug)]
+pub struct RunOutput {
+    pub status: RunStatus,
+    pub stdout: String,
+    pub stderr: String,
+    /// Only available on unix, where it comes from `wait4`.
+    pub usage: Option<ResourceUsage>,
+}
+
+impl RunOutput {
+    pub fn success(&self) -> bool {
+        matches!(&self.status, RunStatus::Exited(status) if status.success())
+    }
+
+    pub const fn timed_out(&self) -> bool {
+        matches!(self.status, RunStatus::TimedOut)
+    }
+
+    /// One-line reason for a failed run: the `error:` line printed by `run_solution!`,
+    /// the message of a panic, or the exit status.
+    pub fn error_message(&self) -> String {
+        let mut lines = self.stderr.lines().map(str::trim);
+
+        while let Some(line) = lines.next() {
+            if let Some(message) = line.strip_prefix("error: ") {
+                return message.to_string();
+            }
+
+            // thread 'main' panicked at src/main.rs:17:9:
+            // <message>
+            if line.contains("panicked at")
+                && let Some(message) = lines.next()
+            {
+                return format!("panicked: {message}");
+            }
+        }
+
+        match &self.status {
+            RunStatus::Exited(status) => format!("exited with {status}"),
+            RunStatus::TimedOut => "timed out".to_string(),
+        }
+    }
+}
+
+/// Runs a command to completion, capturing its output and resource usage.
+///
+/// If `timeout` elapses first, the child is killed and the run is reported as
+/// [`RunStatus::TimedOut`] with whatever output it produced so far.
+pub fn run_measured(command: &mut Command, timeout: Option<Duration>) -> Result<RunOutput> {
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to spawn process")?;
+
+    let stdout = capture(child.stdout.take());
+    let stderr = capture(child.stderr.take());
+
+    let (status, usage) = wait_with_timeout(&mut child, timeout)?;
+
+    Ok(RunOutput {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        usage,
+    })
+}
+
+// Pipes are drained on their own threads so a chatty child never blocks on a full pipe
+pub fn capture(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        String::from_utf8_lossy(&buffer).into_owned()
+    })
+}
+
+#[cfg(unix)]
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> Result<(RunStatus, Option<ResourceUsage>)> {
+    use std::os::unix::process::ExitStatusExt;
+
+    #[allow(clippy::cast_possible_wrap)]
+    let pid = child.id() as libc::pid_t;
+    let start = Instant::now();
+    let mut timed_out = false;
+
+    loop {
+        let mut status = 0;
+        // SAFETY: `rusage` is a plain C struct for which all-zero bytes are a valid value
+        let mut rusage: libc::rusage = unsafe { std::mem::zeroed() };
+        let flags = if timed_out { 0 } else { libc::WNOHANG };
+
+        // SAFETY: `pid` is our own unreaped child, and both out-pointers are valid
+        let result = unsafe { libc::wait4(pid, &raw mut status, flags, &raw mut rusage) };
+
+        if result == pid {
+            let status = if timed_out {
+                RunStatus::TimedOut
+            } else {
+                RunStatus::Exited(ExitStatus::from_raw(status))
+            };
+
+            return Ok((status, Some(usage_from_rusage(&rusage))));
+        }
+
+        if result < 0 {
+            let error = std::io::Error::last_os_error();
+            if error.kind() == std::io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(error).context("Failed to wait for process");
+        }
+
+        if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            child.kill().context("Failed to kill timed out process")?;
+            timed_out = true;
+            continue;
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(unix)]
+#[allow(clippy::cast_sign_loss)]
+fn usage_from_rusage(rusage: &libc::rusage) -> ResourceUsage {
+    let micros = |time: libc::timeval| time.tv_sec as u128 * 1_000_000 + time.tv_usec as u128;
+
+    // Linux reports `ru_maxrss` in kilobytes, macOS in bytes
+    let max_rss = rusage.ru_maxrss as u64;
+    let peak_rss_kb = if cfg!(target_os = "macos") {
+        max_rss / 1024
+    } else {
+        max_rss
+    };
+
+    ResourceUsage {
+        peak_rss_kb,
+        cpu_micros: micros(rusage.ru_utime) + micros(rusage.ru_stime),
+    }
+}
+
+#[cfg(not(unix))]
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> Result<(RunStatus, Option<ResourceUsage>)> {
+    let start = Instant::now();
+
+    loop {
+        if let Some(status) = child.try_wait().context("Failed to wait for process")? {
+            return Ok((RunStatus::Exited(status), None));
+        }
+
+        if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            child.kill().context("Failed to kill timed out process")?;
+            child.wait().context("Failed to wait for process")?;
+            return Ok((RunStatus::TimedOut, None));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::add;
+use super::runner;
+use crate::templates;
+
+pub fn remove_day(day: u8, force: bool) -> Result<()> {
+    if !(1..=25).contains(&day) {
+        anyhow::bail!("Day must be between 1 and 25");
+    }
+
+    if !Path::new("Cargo.toml").exists() {
+        anyhow::bail!("Not in a workspace directory. Run 'aoc-cli init <name>' first.");
+    }
+
+    let day_name = format!("day{day:02}");
+    let day_path = PathBuf::from(&day_name);
+
+    if !day_path.exists() {
+        anyhow::bail!("Day {day} does not exist");
+    }
+
+    let changed = changed_files(&day_path, day);
+    if !changed.is_empty() && !force {
+        anyhow::bail!(
+            "{day_name} has work that would be lost ({}). Use --force to remove it anyway.",
+            changed.join(", ")
+        );
+    }
+
+    println!("Removing {day_name}...");
+
+    fs::remove_dir_all(&day_path).context(format!("Failed to remove {day_name}"))?;
+
+    runner::sync_runner(Path::new("."))?;
+
+    println!("{day_name} removed successfully!");
+
+    Ok(())
+}
+
+/// Files of the day that differ from what `add` generated.
+fn changed_files(day_path: &Path, day: u8) -> Vec<&'static str> {
+    let day_number = format!("{day:02}");
+    let generated = [
+        ("src/lib.rs", add::lib_template(day)),
+        ("src/main.rs", templates::DAY_MAIN_RS),
+        ("answers.txt", templates::DAY_ANSWERS),
+        ("README.md", templates::DAY_README),
+        ("input.txt", ""),
+    ];
+
+    generated
+        .into_iter()
+        .filter(|(file, template)| {
+            fs::read_to_string(day_path.join(file))
+                .is_ok_and(|content| content != template.replace("{DAY}", &day_number))
+        })
+        .map(|(file, _)| file)
+        .collect()
+}
@@ -0,0 +1,516 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::fmt::Write;
+use std::path::Path;
+
+use super::analytics::{DayStatus, DayTiming};
+use super::answers::NO_PART;
+use super::config::BuildSettings;
+use super::summary::Summary;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[value(name = "md", alias = "markdown")]
+    Markdown,
+    Json,
+    Csv,
+    Html,
+}
+
+impl OutputFormat {
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Csv => "csv",
+            Self::Html => "html",
+        }
+    }
+
+    /// Detects the output format from a file extension, if it is a known one.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        match extension.as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            "html" | "htm" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    /// Renders the report. The CSV format only holds the per-day rows; the other
+    /// formats also include the summary section.
+    pub fn render(
+        self,
+        timings: &[DayTiming],
+        settings: BuildSettings,
+        summary: &Summary,
+    ) -> Result<String> {
+        match self {
+            Self::Markdown => Ok(format_markdown(timings, settings, summary)),
+            Self::Json => format_json(timings, settings, summary),
+            Self::Csv => Ok(format_csv(timings, settings)),
+            Self::Html => Ok(format_html(timings, settings, summary)),
+        }
+    }
+}
+
+fn format_markdown(timings: &[DayTiming], settings: BuildSettings, summary: &Summary) -> String {
+    format!(
+        "Build profile: {settings}\n\n{}\n{}",
+        format_table(timings),
+        summary.to_markdown()
+    )
+}
+
+fn format_table(timings: &[DayTiming]) -> String {
+    const HEADERS: [&str; 6] = ["day", "part 1", "part 2", "total", "peak mem", "cpu time"];
+
+    let rows: Vec<[String; 6]> = timings
+        .iter()
+        .map(|timing| {
+            [
+                timing.day.to_string(),
+                ms_cell(timing, timing.part1_ms()),
+                part2_cell(timing),
+                ms_cell(timing, timing.total_ms()),
+                mb_cell(timing.peak_rss_mb()),
+                ms_cell(timing, timing.cpu_ms()),
+            ]
+        })
+        .collect();
+
+    // Wide enough for the header and every cell, e.g. "panicked"
+    let widths: Vec<usize> = (0..HEADERS.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .fold(HEADERS[column].len(), usize::max)
+        })
+        .collect();
+
+    let mut table = String::new();
+    for (header, width) in HEADERS.iter().zip(&widths) {
+        let _ = write!(&mut table, "| {header:<width$} ");
+    }
+    table.push_str("|\n");
+    for width in &widths {
+        let _ = write!(&mut table, "| {} ", "-".repeat(*width));
+    }
+    table.push_str("|\n");
+
+    for row in &rows {
+        for (cell, width) in row.iter().zip(&widths) {
+            let _ = write!(&mut table, "| {cell:>width$} ");
+        }
+        table.push_str("|\n");
+    }
+
+    table
+}
+
+/// Formats a duration cell, showing why it is missing when the day did not finish.
+fn ms_cell(timing: &DayTiming, ms: Option<f64>) -> String {
+    match (ms, timing.status) {
+        (Some(ms), _) => format!("{ms:.2}ms"),
+        (None, DayStatus::Timeout) => "timeout".to_string(),
+        (None, DayStatus::Failed) => "failed".to_string(),
+        (None, DayStatus::Panicked) => "panicked".to_string(),
+        (None, DayStatus::Ok) => "-".to_string(),
+    }
+}
+
+fn part2_cell(timing: &DayTiming) -> String {
+    if timing.no_part2 {
+        NO_PART.to_string()
+    } else {
+        ms_cell(timing, timing.part2_ms())
+    }
+}
+
+fn mb_cell(mb: Option<f64>) -> String {
+    mb.map_or_else(|| "-".to_string(), |mb| format!("{mb:.1}MB"))
+}
+
+fn optional<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    build: BuildSettings,
+    days: &'a [DayTiming],
+    summary: &'a Summary,
+}
+
+fn format_json(
+    timings: &[DayTiming],
+    settings: BuildSettings,
+    summary: &Summary,
+) -> Result<String> {
+    let report = JsonReport {
+        build: settings,
+        days: timings,
+        summary,
+    };
+    let mut json =
+        serde_json::to_string_pretty(&report).context("Failed to serialize timings to JSON")?;
+    json.push('\n');
+
+    Ok(json)
+}
+
+fn format_csv(timings: &[DayTiming], settings: BuildSettings) -> String {
+    let mut csv = String::from(
+        "day,status,part1_micros,part2_micros,total_micros,peak_rss_kb,cpu_micros,profile,target_cpu_native,error\n",
+    );
+
+    for timing in timings {
+        let _ = writeln!(
+            &mut csv,
+            "{},{},{},{},{},{},{},{},{},{}",
+            timing.day,
+            status_label(timing.status),
+            optional(timing.part1_micros),
+            optional(timing.part2_micros),
+            optional(timing.total_micros()),
+            optional(timing.peak_rss_kb),
+            optional(timing.cpu_micros),
+            settings.profile,
+            settings.target_cpu_native,
+            csv_field(&error_field(timing)),
+        );
+    }
+
+    csv
+}
+
+/// The error of a failed day, or the panics of a day that panicked.
+fn error_field(timing: &DayTiming) -> String {
+    if timing.panics.is_empty() {
+        return timing.error.clone().unwrap_or_default();
+    }
+
+    let panics: Vec<String> = timing
+        .panics
+        .iter()
+        .map(|panic| format!("{}: {}", panic.stage, panic.message))
+        .collect();
+    panics.join("; ")
+}
+
+/// Quotes a field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+const fn status_label(status: DayStatus) -> &'static str {
+    match status {
+        DayStatus::Ok => "ok",
+        DayStatus::Timeout => "timeout",
+        DayStatus::Failed => "failed",
+        DayStatus::Panicked => "panicked",
+    }
+}
+
+fn format_html(timings: &[DayTiming], settings: BuildSettings, summary: &Summary) -> String {
+    let mut rows = String::new();
+
+    for timing in timings {
+        let _ = writeln!(
+            &mut rows,
+            "      <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+            timing.day,
+            ms_cell(timing, timing.part1_ms()),
+            part2_cell(timing),
+            ms_cell(timing, timing.total_ms()),
+            mb_cell(timing.peak_rss_mb()),
+            ms_cell(timing, timing.cpu_ms()),
+        );
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Advent of Code timings</title>
+  <style>
+    body {{ font-family: system-ui, sans-serif; margin: 2rem; color: #222; }}
+    table {{ border-collapse: collapse; margin-top: 2rem; }}
+    th, td {{ border: 1px solid #ccc; padding: 0.3rem 0.8rem; text-align: right; }}
+    th {{ background: #f4f4f4; }}
+    .part1 {{ fill: #4e79a7; }}
+    .part2 {{ fill: #f28e2b; }}
+    .grid {{ stroke: #ddd; stroke-width: 1; }}
+    .label {{ font-size: 11px; fill: #555; }}
+  </style>
+</head>
+<body>
+  <h1>Advent of Code timings</h1>
+  <p>Build profile: {settings}</p>
+{chart}
+{summary}
+  <table>
+    <thead>
+      <tr><th>day</th><th>part 1</th><th>part 2</th><th>total</th><th>peak mem</th><th>cpu time</th></tr>
+    </thead>
+    <tbody>
+{rows}    </tbody>
+  </table>
+</body>
+</html>
+"#,
+        chart = format_svg_chart(timings),
+        summary = summary.to_html().trim_end(),
+    )
+}
+
+/// Renders a grouped bar chart of per-part timings as inline SVG, on a log scale
+/// so that microsecond and multi-second days remain readable side by side.
+#[allow(clippy::cast_precision_loss)]
+fn format_svg_chart(timings: &[DayTiming]) -> String {
+    const BAR_WIDTH: f64 = 12.0;
+    const GROUP_GAP: f64 = 10.0;
+    const PLOT_HEIGHT: f64 = 300.0;
+    const MARGIN_LEFT: f64 = 70.0;
+    const MARGIN_BOTTOM: f64 = 30.0;
+    const MARGIN_TOP: f64 = 20.0;
+    // Keeps bars at the bottom decade visible
+    const MIN_BAR_HEIGHT: f64 = 2.0;
+
+    // Log scale bounds in whole decades of microseconds, at least 1µs
+    let log_value = |micros: u128| (micros.max(1) as f64).log10();
+    let values = timings
+        .iter()
+        .flat_map(|timing| [timing.part1_micros, timing.part2_micros])
+        .flatten();
+
+    // Every day timed out, failed or panicked: there is no scale to draw
+    if values.clone().next().is_none() {
+        return "  <p>No part finished, so there is no chart.</p>".to_string();
+    }
+
+    let min_decade = values
+        .clone()
+        .map(log_value)
+        .fold(f64::INFINITY, f64::min)
+        .floor();
+    let max_decade = values.map(log_value).fold(0.0, f64::max).ceil();
+    let max_decade = if max_decade <= min_decade {
+        min_decade + 1.0
+    } else {
+        max_decade
+    };
+
+    let y_for = |micros: u128| {
+        let ratio = (log_value(micros) - min_decade) / (max_decade - min_decade);
+        MARGIN_TOP + PLOT_HEIGHT * (1.0 - ratio.clamp(0.0, 1.0))
+    };
+
+    let group_width = BAR_WIDTH * 2.0 + GROUP_GAP;
+    let width = MARGIN_LEFT + group_width * timings.len() as f64 + GROUP_GAP;
+    let height = MARGIN_TOP + PLOT_HEIGHT + MARGIN_BOTTOM;
+    let baseline = MARGIN_TOP + PLOT_HEIGHT;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        &mut svg,
+        r#"  <svg xmlns="http://www.w3.org/2000/svg" width="{width:.0}" height="{height:.0}" role="img" aria-label="Per-day timings (log scale)">"#
+    );
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    for decade in (min_decade as u32)..=(max_decade as u32) {
+        let micros = 10_u128.pow(decade);
+        let y = y_for(micros);
+        let _ = writeln!(
+            &mut svg,
+            r#"    <line class="grid" x1="{MARGIN_LEFT}" y1="{y:.1}" x2="{width:.0}" y2="{y:.1}"/>"#
+        );
+        let _ = writeln!(
+            &mut svg,
+            r#"    <text class="label" x="{:.0}" y="{:.1}" text-anchor="end">{}</text>"#,
+            MARGIN_LEFT - 6.0,
+            y + 4.0,
+            format_duration_label(micros)
+        );
+    }
+
+    for (index, timing) in timings.iter().enumerate() {
+        let x = MARGIN_LEFT + GROUP_GAP + group_width * index as f64;
+
+        for (offset, class, micros) in [
+            (0.0, "part1", timing.part1_micros),
+            (BAR_WIDTH, "part2", timing.part2_micros),
+        ] {
+            let Some(micros) = micros else {
+                continue;
+            };
+            let bar_height = (baseline - y_for(micros)).max(MIN_BAR_HEIGHT);
+            let _ = writeln!(
+                &mut svg,
+                r#"    <rect class="{class}" x="{:.1}" y="{:.1}" width="{BAR_WIDTH}" height="{bar_height:.1}"><title>Day {} {class}: {:.3}ms</title></rect>"#,
+                x + offset,
+                baseline - bar_height,
+                timing.day,
+                micros as f64 / 1000.0
+            );
+        }
+
+        let _ = writeln!(
+            &mut svg,
+            r#"    <text class="label" x="{:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            x + BAR_WIDTH,
+            baseline + 16.0,
+            timing.day
+        );
+    }
+
+    svg.push_str("  </svg>");
+    svg
+}
+
+fn format_duration_label(micros: u128) -> String {
+    match micros {
+        0..1_000 => format!("{micros}µs"),
+        1_000..1_000_000 => format!("{}ms", micros / 1_000),
+        _ => format!("{}s", micros / 1_000_000),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::analytics::PartPanic;
+
+    fn timing(day: u8, status: DayStatus, part1: Option<u128>, part2: Option<u128>) -> DayTiming {
+        DayTiming {
+            day,
+            status,
+            part1_micros: part1,
+            part2_micros: part2,
+            no_part2: false,
+            peak_rss_kb: None,
+            cpu_micros: None,
+            error: None,
+            panics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_svg_chart_without_timings() {
+        let timings = [
+            timing(1, DayStatus::Timeout, None, None),
+            timing(2, DayStatus::Timeout, None, None),
+        ];
+
+        let chart = format_svg_chart(&timings);
+        assert!(!chart.contains("<svg"));
+        assert!(!chart.contains("NaN"));
+    }
+
+    #[test]
+    fn test_svg_chart_bars() {
+        let timings = [timing(1, DayStatus::Ok, Some(10), Some(10_000))];
+
+        let chart = format_svg_chart(&timings);
+        assert_eq!(chart.matches("<rect").count(), 2);
+        assert!(!chart.contains("NaN"));
+        // The smallest bar sits on the lowest decade but stays visible
+        assert!(chart.contains(r#"height="2.0""#));
+    }
+
+    fn report_timings() -> Vec<DayTiming> {
+        let mut last_day = timing(25, DayStatus::Ok, Some(1_500), None);
+        last_day.no_part2 = true;
+        last_day.peak_rss_kb = Some(2_048);
+
+        let mut panicked = timing(3, DayStatus::Panicked, None, Some(200));
+        panicked.panics = vec![PartPanic {
+            stage: "part 1".to_string(),
+            message: "no start tile, \"S\"".to_string(),
+        }];
+
+        vec![
+            timing(1, DayStatus::Ok, Some(1_000), Some(2_500)),
+            panicked,
+            timing(4, DayStatus::Timeout, None, None),
+            last_day,
+        ]
+    }
+
+    fn render(format: OutputFormat) -> String {
+        let timings = report_timings();
+        let summary = Summary::new(&timings, &[]);
+        format
+            .render(&timings, BuildSettings::default(), &summary)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_markdown_report() {
+        let report = render(OutputFormat::Markdown);
+
+        assert!(report.starts_with("Build profile: release\n"));
+        assert!(report.contains(
+            "\
+| day | part 1   | part 2  | total    | peak mem | cpu time |
+| --- | -------- | ------- | -------- | -------- | -------- |
+|   1 |   1.00ms |  2.50ms |   3.50ms |        - |        - |
+|   3 | panicked |  0.20ms | panicked |        - | panicked |
+|   4 |  timeout | timeout |  timeout |        - |  timeout |
+|  25 |   1.50ms |       — |   1.50ms |    2.0MB |        - |
+"
+        ));
+        assert!(report.contains("## Summary"));
+    }
+
+    #[test]
+    fn test_json_report() {
+        let report: serde_json::Value = serde_json::from_str(&render(OutputFormat::Json)).unwrap();
+
+        assert_eq!(report["build"]["profile"], "release");
+        assert_eq!(report["days"][0]["part2_micros"], 2_500);
+        assert_eq!(report["days"][1]["status"], "panicked");
+        assert_eq!(report["days"][1]["panics"][0]["stage"], "part 1");
+        assert_eq!(report["days"][3]["no_part2"], true);
+        // Skipped when false
+        assert!(report["days"][0].get("no_part2").is_none());
+        assert!(report["summary"].is_object());
+    }
+
+    #[test]
+    fn test_csv_report() {
+        let report = render(OutputFormat::Csv);
+        let lines: Vec<&str> = report.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[1], "1,ok,1000,2500,3500,,,release,false,");
+        assert_eq!(
+            lines[2],
+            r#"3,panicked,,200,,,,release,false,"part 1: no start tile, ""S""""#
+        );
+        assert_eq!(lines[3], "4,timeout,,,,,,release,false,");
+        assert_eq!(lines[4], "25,ok,1500,,1500,2048,,release,false,");
+    }
+
+    #[test]
+    fn test_html_report() {
+        let report = render(OutputFormat::Html);
+
+        assert!(report.starts_with("<!DOCTYPE html>"));
+        assert!(report.contains("<p>Build profile: release</p>"));
+        assert!(report.contains(
+            "<tr><td>1</td><td>1.00ms</td><td>2.50ms</td><td>3.50ms</td><td>-</td><td>-</td></tr>"
+        ));
+        assert!(report.contains(&format!("<td>25</td><td>1.50ms</td><td>{NO_PART}</td>")));
+        assert!(report.contains("<svg"));
+    }
+}
@@ -0,0 +1,428 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::analytics::{PartPanic, micros_to_ms, parse_panics, parse_timing};
+use super::answers::{Answers, Check, NO_PART, PartOutcome};
+use super::build;
+use super::config::BuildSettings;
+use super::process;
+use super::runner;
+use super::time::{detect_current_day, find_workspace_root};
+
+/// One input a day can be run against, with the answers file stored next to it.
+struct InputFile {
+    label: String,
+    path: PathBuf,
+    answers: PathBuf,
+    /// The day's own `input.txt`, which is compiled into the binary.
+    embedded: bool,
+}
+
+struct InputResult {
+    label: String,
+    outcome: Result<InputRun, String>,
+}
+
+/// A run that printed at least one answer. If it failed, `error` holds its report
+/// and `panics` the parts that panicked.
+struct InputRun {
+    expected: Answers,
+    actual: Answers,
+    total_micros: Option<u128>,
+    panics: Vec<PartPanic>,
+    error: Option<String>,
+}
+
+impl InputRun {
+    fn parts(&self) -> [PartOutcome; 2] {
+        let failed = self.error.is_some();
+        [
+            PartOutcome::of(
+                1,
+                self.expected.part1.as_deref(),
+                self.actual.part1.as_deref(),
+                &self.panics,
+                failed,
+            ),
+            PartOutcome::of(
+                2,
+                self.expected.part2.as_deref(),
+                self.actual.part2.as_deref(),
+                &self.panics,
+                failed,
+            ),
+        ]
+    }
+}
+
+impl InputResult {
+    fn passed(&self) -> bool {
+        self.outcome
+            .as_ref()
+            .is_ok_and(|run| run.parts().iter().all(|part| part.passed()))
+    }
+}
+
+/// Runs a day against its own input, or with `all_inputs` against every input found
+/// in `dayXX/inputs/*.txt` and `inputs/<name>/dayXX.txt` as well, and checks the
+/// answers against the `answers.txt` / `<input>.answers.txt` file next to each input.
+pub fn run_day(day: Option<u8>, all_inputs: bool) -> Result<()> {
+    let day = match day {
+        Some(d) => {
+            if !(1..=25).contains(&d) {
+                anyhow::bail!("Day must be between 1 and 25");
+            }
+            d
+        }
+        None => detect_current_day()?,
+    };
+
+    let workspace_root = find_workspace_root()?;
+
+    let day_name = format!("day{day:02}");
+    let day_path = workspace_root.join(&day_name);
+
+    if !day_path.exists() {
+        anyhow::bail!("Day {day} does not exist. Run 'aoc-cli add {day}' first.");
+    }
+
+    let inputs = find_inputs(&workspace_root, &day_name, all_inputs)?;
+
+    if inputs.iter().any(|input| !input.embedded) && !utils_supports_input(&workspace_root) {
+        anyhow::bail!(
+            "The utils crate cannot read other inputs yet. Run 'aoc-cli update utils' first."
+        );
+    }
+
+    let settings = BuildSettings::load(&workspace_root)?;
+
+    println!("Building {day_name} ({settings})...\n");
+
+    let build = build::build_package(&workspace_root, settings, &day_name)?;
+
+    if let Some(errors) = build.errors_for(&day_name) {
+        anyhow::bail!("Failed to compile {day_name}:\n{}", errors.join("\n"));
+    }
+
+    let binary = build
+        .binary(&day_name)
+        .context(format!("Failed to build {day_name}"))?;
+
+    println!(
+        "\nRunning {day_name} against {} input(s)...\n",
+        inputs.len()
+    );
+
+    let mut results = Vec::new();
+
+    for input in &inputs {
+        let mut command = Command::new(binary);
+        command.arg("--timing").current_dir(&day_path);
+
+        if !input.embedded {
+            command.arg("--input").arg(&input.path);
+        }
+
+        let output = process::run_measured(&mut command, None)
+            .context(format!("Failed to run {day_name} on {}", input.label))?;
+
+        let actual = Answers::from_output(&output.stdout);
+
+        // A panic in one part does not stop the other, whose answer is still checked
+        let outcome = if !output.success() && actual.part1.is_none() && actual.part2.is_none() {
+            Err(output.stderr.trim().to_string())
+        } else {
+            Ok(InputRun {
+                expected: Answers::load(&input.answers)?.unwrap_or_default(),
+                actual,
+                total_micros: parse_timing(&output.stdout, day, None)
+                    .and_then(|timing| timing.total_micros()),
+                panics: parse_panics(&output.stdout),
+                error: (!output.success()).then(|| output.stderr.trim().to_string()),
+            })
+        };
+
+        results.push(InputResult {
+            label: input.label.clone(),
+            outcome,
+        });
+    }
+
+    print_results("input", &results);
+
+    let failures = results.iter().filter(|result| !result.passed()).count();
+    if failures > 0 {
+        anyhow::bail!("{failures} input(s) failed");
+    }
+
+    Ok(())
+}
+
+/// Runs every day registered with the workspace runner in a single process and
+/// checks the answers against each day's `answers.txt`.
+pub fn run_all() -> Result<()> {
+    let workspace_root = find_workspace_root()?;
+
+    runner::sync_runner(&workspace_root)?;
+
+    let days = runner::registered_days(&workspace_root);
+    if days.is_empty() {
+        anyhow::bail!(
+            "No days are linked into the runner. Add one with 'aoc-cli add <day>', or run \
+             'aoc-cli update utils --migrate' to move existing days into src/lib.rs."
+        );
+    }
+
+    let settings = BuildSettings::load(&workspace_root)?;
+
+    println!(
+        "Building runner with {} day(s) ({settings})...\n",
+        days.len()
+    );
+
+    let build = build::build_package(&workspace_root, settings, "runner")?;
+
+    if !build.errors.is_empty() {
+        let errors: Vec<&str> = build
+            .errors
+            .values()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+        anyhow::bail!("Failed to compile the runner:\n{}", errors.join("\n"));
+    }
+
+    let binary = build.binary("runner").context("Failed to build runner")?;
+
+    println!("\nRunning {} day(s) in one process...\n", days.len());
+
+    let output = process::run_measured(Command::new(binary).current_dir(&workspace_root), None)
+        .context("Failed to run runner")?;
+
+    let mut results = Vec::new();
+    let mut total_micros = None;
+
+    for block in output.stdout.split("DAY:").skip(1) {
+        let (day, block) = block.split_once('\n').unwrap_or((block, ""));
+        let Ok(day) = day.trim().parse::<u8>() else {
+            continue;
+        };
+
+        if let Some(total) = block.lines().find_map(|line| line.strip_prefix("TOTAL:")) {
+            total_micros = total.trim().parse::<u128>().ok();
+        }
+
+        let errors: Vec<&str> = block
+            .lines()
+            .filter_map(|line| line.strip_prefix("ERROR:"))
+            .collect();
+
+        // A failing part is reported with `ERROR:` after the other part's answer
+        let outcome = if let Some(timing) = parse_timing(block, day, None) {
+            Ok(InputRun {
+                expected: Answers::load(&workspace_root.join(format!("day{day:02}/answers.txt")))?
+                    .unwrap_or_default(),
+                actual: Answers::from_output(block),
+                total_micros: timing.total_micros(),
+                panics: parse_panics(block),
+                error: (!errors.is_empty()).then(|| errors.join("\n")),
+            })
+        } else if !errors.is_empty() {
+            Err(errors.join("\n"))
+        } else {
+            // The day that was running when the runner crashed
+            Err(output.error_message())
+        };
+
+        results.push(InputResult {
+            label: format!("day{day:02}"),
+            outcome,
+        });
+    }
+
+    print_results("day", &results);
+
+    if let Some(total) = total_micros {
+        println!("\nTotal: {:.3}ms", micros_to_ms(total));
+    }
+
+    let failures = results.iter().filter(|result| !result.passed()).count();
+    if failures > 0 {
+        anyhow::bail!("{failures} day(s) failed");
+    }
+
+    if !output.success() {
+        anyhow::bail!("The runner failed:\n{}", output.error_message());
+    }
+
+    Ok(())
+}
+
+fn find_inputs(workspace_root: &Path, day_name: &str, all_inputs: bool) -> Result<Vec<InputFile>> {
+    let mut inputs = vec![InputFile {
+        label: format!("{day_name}/input.txt"),
+        path: workspace_root.join(day_name).join("input.txt"),
+        answers: workspace_root.join(day_name).join("answers.txt"),
+        embedded: true,
+    }];
+
+    if !all_inputs {
+        return Ok(inputs);
+    }
+
+    // dayXX/inputs/<name>.txt, answers in dayXX/inputs/<name>.answers.txt
+    let day_inputs = workspace_root.join(day_name).join("inputs");
+    for path in sorted_entries(&day_inputs)? {
+        let is_input = path.extension().is_some_and(|ext| ext == "txt")
+            && !path.to_string_lossy().ends_with(".answers.txt");
+
+        if is_input {
+            inputs.push(InputFile {
+                label: relative_label(workspace_root, &path),
+                answers: path.with_extension("answers.txt"),
+                path,
+                embedded: false,
+            });
+        }
+    }
+
+    // inputs/<name>/dayXX.txt, answers in inputs/<name>/dayXX.answers.txt
+    for directory in sorted_entries(&workspace_root.join("inputs"))? {
+        let path = directory.join(format!("{day_name}.txt"));
+
+        if path.is_file() {
+            inputs.push(InputFile {
+                label: relative_label(workspace_root, &path),
+                answers: path.with_extension("answers.txt"),
+                path,
+                embedded: false,
+            });
+        }
+    }
+
+    if inputs.len() == 1 {
+        println!(
+            "No other inputs found in {day_name}/inputs/*.txt or inputs/<name>/{day_name}.txt\n"
+        );
+    }
+
+    Ok(inputs)
+}
+
+fn sorted_entries(directory: &Path) -> Result<Vec<PathBuf>> {
+    if !directory.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(directory)
+        .context(format!("Failed to read {}", directory.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    entries.sort();
+
+    Ok(entries)
+}
+
+fn relative_label(workspace_root: &Path, path: &Path) -> String {
+    path.strip_prefix(workspace_root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Workspaces created before `--input` existed embed their input unconditionally.
+fn utils_supports_input(workspace_root: &Path) -> bool {
+    fs::read_to_string(workspace_root.join("utils/src/lib.rs"))
+        .is_ok_and(|lib| lib.contains("\"--input\""))
+}
+
+fn print_results(label_header: &'static str, results: &[InputResult]) {
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|result| match &result.outcome {
+            Ok(run) => {
+                let [part1, part2] = run.parts();
+                [
+                    result.label.clone(),
+                    answer_cell(run.actual.part1.as_deref(), part1),
+                    answer_cell(run.actual.part2.as_deref(), part2),
+                    run.total_micros.map_or_else(
+                        || "-".to_string(),
+                        |micros| format!("{:.3}ms", micros_to_ms(micros)),
+                    ),
+                ]
+            }
+            Err(_) => [
+                result.label.clone(),
+                "crashed".to_string(),
+                "crashed".to_string(),
+                "-".to_string(),
+            ],
+        })
+        .collect();
+
+    let headers = [label_header, "part 1", "part 2", "time"];
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([headers[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let format_row = |cells: [&str; 4]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        format!("| {} |", cells.join(" | "))
+    };
+
+    println!("{}", format_row(headers));
+    let separators: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+    println!("| {} |", separators.join(" | "));
+
+    for row in &rows {
+        println!("{}", format_row([&row[0], &row[1], &row[2], &row[3]]));
+    }
+
+    for result in results {
+        match &result.outcome {
+            Ok(run) => {
+                let parts = run.parts();
+                for (part, expected, actual, outcome) in [
+                    (1, &run.expected.part1, &run.actual.part1, parts[0]),
+                    (2, &run.expected.part2, &run.actual.part2, parts[1]),
+                ] {
+                    if outcome == PartOutcome::Checked(Check::Fail) {
+                        println!(
+                            "\n{} part {part}: expected {}, got {}",
+                            result.label,
+                            expected.as_deref().unwrap_or_default(),
+                            actual.as_deref().unwrap_or("no answer")
+                        );
+                    }
+                }
+
+                if let Some(error) = &run.error {
+                    println!("\n{} failed:\n{error}", result.label);
+                }
+            }
+            Err(stderr) => println!("\n{} failed to run:\n{stderr}", result.label),
+        }
+    }
+}
+
+fn answer_cell(answer: Option<&str>, outcome: PartOutcome) -> String {
+    match outcome {
+        PartOutcome::Checked(Check::NoPart) => NO_PART.to_string(),
+        PartOutcome::Checked(check) => format!("{} ({})", answer.unwrap_or("-"), check.label()),
+        PartOutcome::Panicked | PartOutcome::Crashed => outcome.label().to_string(),
+    }
+}
@@ -0,0 +1,75 @@
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::templates;
+
+/// Days whose `src/lib.rs` registers the solution with `utils::register_day!`.
+pub(super) fn registered_days(workspace_root: &Path) -> Vec<u8> {
+    (1..=25)
+        .filter(|day| {
+            fs::read_to_string(workspace_root.join(format!("day{day:02}/src/lib.rs")))
+                .is_ok_and(|lib| lib.contains("register_day!"))
+        })
+        .collect()
+}
+
+/// Regenerates the `runner` crate so it links every registered day, and adds it to
+/// the workspace members of workspaces created before it existed.
+pub(super) fn sync_runner(workspace_root: &Path) -> Result<()> {
+    let days = registered_days(workspace_root);
+
+    let mut dependencies = String::new();
+    let mut entries = String::new();
+    for day in &days {
+        let _ = writeln!(
+            dependencies,
+            "day{day:02} = {{ path = \"../day{day:02}\" }}"
+        );
+        let _ = write!(entries, "\n        ({day}, &day{day:02}::DAY),");
+    }
+    if !entries.is_empty() {
+        entries.push_str("\n    ");
+    }
+
+    let runner_path = workspace_root.join("runner");
+    fs::create_dir_all(runner_path.join("src")).context("Failed to create runner directory")?;
+
+    let cargo_content = templates::RUNNER_CARGO_TOML.replace("{DEPENDENCIES}\n", &dependencies);
+    write_if_changed(&runner_path.join("Cargo.toml"), &cargo_content)?;
+
+    let main_content = templates::RUNNER_MAIN_RS.replace("{DAYS}", &entries);
+    write_if_changed(&runner_path.join("src/main.rs"), &main_content)?;
+
+    add_workspace_member(workspace_root)
+}
+
+/// Unchanged files are left alone so cargo does not rebuild the runner needlessly.
+fn write_if_changed(path: &Path, content: &str) -> Result<()> {
+    if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+        return Ok(());
+    }
+
+    fs::write(path, content).context(format!("Failed to write {}", path.display()))
+}
+
+fn add_workspace_member(workspace_root: &Path) -> Result<()> {
+    let cargo_path = workspace_root.join("Cargo.toml");
+    let content = fs::read_to_string(&cargo_path).context("Failed to read workspace Cargo.toml")?;
+
+    if content.contains("\"runner\"") {
+        return Ok(());
+    }
+
+    if !content.contains("\"utils\"]") {
+        println!("Add \"runner\" to the workspace members in Cargo.toml to use 'run --all'");
+        return Ok(());
+    }
+
+    let updated = content.replacen("\"utils\"]", "\"utils\", \"runner\"]", 1);
+    fs::write(&cargo_path, updated).context("Failed to write workspace Cargo.toml")?;
+    println!("Added runner to the workspace members");
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::fmt::Write;
+
+use super::analytics::micros_to_ms;
+
+/// One span reported by a solution run with `--spans`.
+struct SpanLine<'a> {
+    depth: usize,
+    calls: u32,
+    micros: u128,
+    name: &'a str,
+}
+
+/// Extracts the `SPAN:<depth>:<calls>:<micros>:<name>` lines printed by the utils
+/// crate, in tree order.
+fn parse_spans(output: &str) -> Vec<SpanLine<'_>> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.strip_prefix("SPAN:")?.splitn(4, ':');
+
+            Some(SpanLine {
+                depth: fields.next()?.parse().ok()?,
+                calls: fields.next()?.parse().ok()?,
+                micros: fields.next()?.parse().ok()?,
+                name: fields.next()?,
+            })
+        })
+        .collect()
+}
+
+/// Renders the spans found in a solution's output as an indented tree, with each
+/// span's share of the total time. Returns `None` if the output has no spans.
+pub fn render_span_tree(output: &str) -> Option<String> {
+    let spans = parse_spans(output);
+
+    if spans.is_empty() {
+        return None;
+    }
+
+    let total: u128 = spans
+        .iter()
+        .filter(|span| span.depth == 0)
+        .map(|span| span.micros)
+        .sum();
+
+    let width = spans
+        .iter()
+        .map(|span| span.depth * 2 + span.name.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let mut tree = String::from("Spans:\n");
+
+    for span in &spans {
+        let label = format!("{}{}", "  ".repeat(span.depth), span.name);
+
+        #[allow(clippy::cast_precision_loss)]
+        let percent = if total == 0 {
+            0.0
+        } else {
+            span.micros as f64 / total as f64 * 100.0
+        };
+
+        let _ = write!(
+            tree,
+            "  {label:<width$}  {:>10.3}ms  {percent:>5.1}%",
+            micros_to_ms(span.micros)
+        );
+
+        if span.calls > 1 {
+            let _ = write!(tree, "  ({} calls)", span.calls);
+        }
+
+        tree.push('\n');
+    }
+
+    Some(tree)
+}
@@ -0,0 +1,479 @@
+use serde::Serialize;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+use super::analytics::{DayStatus, DayTiming, micros_to_ms};
+
+const SLOWEST_DAYS: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetScope {
+    /// Sum of every day's runtime.
+    Total,
+    /// Each day's runtime, both parts together.
+    Day,
+    /// Each part on its own.
+    Part,
+    /// One day's runtime, both parts together, e.g. `day05`.
+    OneDay(u8),
+}
+
+impl fmt::Display for BudgetScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Total => f.write_str("total"),
+            Self::Day => f.write_str("day"),
+            Self::Part => f.write_str("part"),
+            Self::OneDay(day) => write!(f, "day{day:02}"),
+        }
+    }
+}
+
+/// Serialized as written on the command line, e.g. `"total"` or `"day05"`.
+impl Serialize for BudgetScope {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A single performance limit, parsed from `--budget <scope>=<duration>`,
+/// e.g. `total=1s`, `part=100ms` or `day05=10ms`.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BudgetLimit {
+    pub scope: BudgetScope,
+    pub micros: u128,
+}
+
+impl FromStr for BudgetLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (scope, duration) = s
+            .split_once('=')
+            .ok_or_else(|| format!("expected <total|day|part|dayNN>=<duration>, got '{s}'"))?;
+
+        let scope = match scope.trim() {
+            "total" => BudgetScope::Total,
+            "day" => BudgetScope::Day,
+            "part" => BudgetScope::Part,
+            other => match other.strip_prefix("day").map(str::parse::<u8>) {
+                Some(Ok(day @ 1..=25)) => BudgetScope::OneDay(day),
+                Some(_) => {
+                    return Err(format!(
+                        "invalid day in budget '{other}', expected day01 to day25"
+                    ));
+                }
+                None => {
+                    return Err(format!(
+                        "unknown budget '{other}', expected total, day, part or dayNN"
+                    ));
+                }
+            },
+        };
+
+        Ok(Self {
+            scope,
+            micros: parse_duration_micros(duration.trim())?,
+        })
+    }
+}
+
+impl fmt::Display for BudgetLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} under {}", self.scope, format_micros(self.micros))
+    }
+}
+
+/// Parses durations such as `1s`, `100ms`, `250us` or `1.5s` into microseconds.
+fn parse_duration_micros(duration: &str) -> Result<u128, String> {
+    let split = duration
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .ok_or_else(|| format!("missing unit in '{duration}', expected s, ms or us"))?;
+    let (value, unit) = duration.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration '{duration}'"))?;
+
+    let scale = match unit {
+        "s" => 1_000_000.0,
+        "ms" => 1_000.0,
+        "us" | "µs" => 1.0,
+        other => return Err(format!("unknown unit '{other}', expected s, ms or us")),
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Ok((value * scale).round() as u128)
+}
+
+fn format_micros(micros: u128) -> String {
+    if micros >= 1_000_000 && micros.is_multiple_of(1_000) {
+        format!("{}s", micros_to_ms(micros) / 1000.0)
+    } else {
+        format!("{}ms", micros_to_ms(micros))
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayFailure {
+    pub day: u8,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayPanic {
+    pub day: u8,
+    pub stage: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DayShare {
+    pub day: u8,
+    pub total_micros: u128,
+    pub percent: f64,
+}
+
+/// Aggregate statistics over an analytics run, with the budget check if one was given.
+#[derive(Debug, Serialize)]
+pub struct Summary {
+    pub total_micros: u128,
+    pub days_run: usize,
+    pub timeouts: Vec<u8>,
+    /// Days whose solution returned an error or crashed, with the reason.
+    pub failures: Vec<DayFailure>,
+    /// Parts that panicked, kept apart from failures since the other parts still ran.
+    pub panics: Vec<DayPanic>,
+    /// Every finished day's share of the total, slowest first.
+    pub shares: Vec<DayShare>,
+    pub budget: Vec<BudgetLimit>,
+    pub violations: Vec<String>,
+}
+
+impl Summary {
+    pub fn new(timings: &[DayTiming], budget: &[BudgetLimit]) -> Self {
+        let total_micros: u128 = timings.iter().filter_map(DayTiming::total_micros).sum();
+
+        let timeouts: Vec<u8> = timings
+            .iter()
+            .filter(|timing| timing.status == DayStatus::Timeout)
+            .map(|timing| timing.day)
+            .collect();
+
+        let failures: Vec<DayFailure> = timings
+            .iter()
+            .filter(|timing| timing.status == DayStatus::Failed)
+            .map(|timing| DayFailure {
+                day: timing.day,
+                error: timing.error.clone().unwrap_or_default(),
+            })
+            .collect();
+
+        let panics: Vec<DayPanic> = timings
+            .iter()
+            .flat_map(|timing| {
+                timing.panics.iter().map(|panic| DayPanic {
+                    day: timing.day,
+                    stage: panic.stage.clone(),
+                    message: panic.message.clone(),
+                })
+            })
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut shares: Vec<DayShare> = timings
+            .iter()
+            .filter_map(|timing| {
+                let day_micros = timing.total_micros()?;
+                Some(DayShare {
+                    day: timing.day,
+                    total_micros: day_micros,
+                    percent: if total_micros == 0 {
+                        0.0
+                    } else {
+                        day_micros as f64 / total_micros as f64 * 100.0
+                    },
+                })
+            })
+            .collect();
+        shares.sort_by_key(|share| std::cmp::Reverse(share.total_micros));
+
+        let violations = check_budget(timings, total_micros, &timeouts, &failures, &panics, budget);
+
+        Self {
+            total_micros,
+            days_run: timings.len(),
+            timeouts,
+            failures,
+            panics,
+            shares,
+            budget: budget.to_vec(),
+            violations,
+        }
+    }
+
+    pub fn budget_exceeded(&self) -> bool {
+        !self.violations.is_empty()
+    }
+
+    fn budget_label(&self) -> Option<&'static str> {
+        if self.budget.is_empty() {
+            None
+        } else if self.budget_exceeded() {
+            Some("FAIL")
+        } else {
+            Some("pass")
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut text = String::from("## Summary\n\n");
+
+        let _ = writeln!(
+            text,
+            "- Total runtime: {:.3}ms over {} day(s)",
+            micros_to_ms(self.total_micros),
+            self.days_run
+        );
+
+        let slowest: Vec<String> = self
+            .shares
+            .iter()
+            .take(SLOWEST_DAYS)
+            .map(|share| {
+                format!(
+                    "day {} ({:.3}ms, {:.1}%)",
+                    share.day,
+                    micros_to_ms(share.total_micros),
+                    share.percent
+                )
+            })
+            .collect();
+        if !slowest.is_empty() {
+            let _ = writeln!(text, "- Slowest days: {}", slowest.join(", "));
+        }
+
+        if !self.timeouts.is_empty() {
+            let days: Vec<String> = self.timeouts.iter().map(u8::to_string).collect();
+            let _ = writeln!(text, "- Timed out: day {}", days.join(", day "));
+        }
+
+        for failure in &self.failures {
+            let _ = writeln!(text, "- Day {} failed: {}", failure.day, failure.error);
+        }
+
+        for panic in &self.panics {
+            let _ = writeln!(
+                text,
+                "- Day {} {}: {}",
+                panic.day, panic.stage, panic.message
+            );
+        }
+
+        if let Some(label) = self.budget_label() {
+            let limits: Vec<String> = self.budget.iter().map(ToString::to_string).collect();
+            let _ = writeln!(text, "- Budget ({}): {label}", limits.join(", "));
+
+            for violation in &self.violations {
+                let _ = writeln!(text, "  - {violation}");
+            }
+        }
+
+        text.push_str("\n| day | share of total |\n| --- | -------------- |\n");
+        for share in &self.shares {
+            let _ = writeln!(text, "| {:>3} | {:>13.1}% |", share.day, share.percent);
+        }
+
+        text
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("  <h2>Summary</h2>\n  <ul>\n");
+
+        let _ = writeln!(
+            html,
+            "    <li>Total runtime: {:.3}ms over {} day(s)</li>",
+            micros_to_ms(self.total_micros),
+            self.days_run
+        );
+
+        for share in self.shares.iter().take(SLOWEST_DAYS) {
+            let _ = writeln!(
+                html,
+                "    <li>Day {}: {:.3}ms ({:.1}% of total)</li>",
+                share.day,
+                micros_to_ms(share.total_micros),
+                share.percent
+            );
+        }
+
+        for failure in &self.failures {
+            let _ = writeln!(
+                html,
+                "    <li>Day {} failed: {}</li>",
+                failure.day,
+                escape_html(&failure.error)
+            );
+        }
+
+        for panic in &self.panics {
+            let _ = writeln!(
+                html,
+                "    <li>Day {} {}: {}</li>",
+                panic.day,
+                panic.stage,
+                escape_html(&panic.message)
+            );
+        }
+
+        if let Some(label) = self.budget_label() {
+            let limits: Vec<String> = self.budget.iter().map(ToString::to_string).collect();
+            let _ = writeln!(
+                html,
+                "    <li>Budget ({}): <strong>{label}</strong>",
+                limits.join(", ")
+            );
+
+            if !self.violations.is_empty() {
+                html.push_str("      <ul>\n");
+                for violation in &self.violations {
+                    let _ = writeln!(html, "        <li>{violation}</li>");
+                }
+                html.push_str("      </ul>\n");
+            }
+
+            html.push_str("    </li>\n");
+        }
+
+        html.push_str("  </ul>\n");
+        html
+    }
+}
+
+fn check_budget(
+    timings: &[DayTiming],
+    total_micros: u128,
+    timeouts: &[u8],
+    failures: &[DayFailure],
+    panics: &[DayPanic],
+    budget: &[BudgetLimit],
+) -> Vec<String> {
+    let mut violations = Vec::new();
+
+    if budget.is_empty() {
+        return violations;
+    }
+
+    // A day that never finished, failed or panicked cannot be within any budget
+    for day in timeouts {
+        violations.push(format!("day {day} timed out"));
+    }
+
+    for failure in failures {
+        violations.push(format!("day {} failed", failure.day));
+    }
+
+    for panic in panics {
+        violations.push(format!("day {} {} panicked", panic.day, panic.stage));
+    }
+
+    for limit in budget {
+        match limit.scope {
+            BudgetScope::Total => {
+                if total_micros > limit.micros {
+                    violations.push(format!(
+                        "total {:.3}ms is over {}",
+                        micros_to_ms(total_micros),
+                        format_micros(limit.micros)
+                    ));
+                }
+            }
+            BudgetScope::Day => {
+                for timing in timings {
+                    if let Some(day_micros) = timing.total_micros()
+                        && day_micros > limit.micros
+                    {
+                        violations.push(format!(
+                            "day {} takes {:.3}ms, over {} per day",
+                            timing.day,
+                            micros_to_ms(day_micros),
+                            format_micros(limit.micros)
+                        ));
+                    }
+                }
+            }
+            BudgetScope::OneDay(day) => {
+                if let Some(day_micros) = timings
+                    .iter()
+                    .find(|timing| timing.day == day)
+                    .and_then(DayTiming::total_micros)
+                    && day_micros > limit.micros
+                {
+                    violations.push(format!(
+                        "day {day} takes {:.3}ms, over {}",
+                        micros_to_ms(day_micros),
+                        format_micros(limit.micros)
+                    ));
+                }
+            }
+            BudgetScope::Part => {
+                for timing in timings {
+                    for (part, micros) in [(1, timing.part1_micros), (2, timing.part2_micros)] {
+                        if let Some(micros) = micros
+                            && micros > limit.micros
+                        {
+                            violations.push(format!(
+                                "day {} part {part} takes {:.3}ms, over {} per part",
+                                timing.day,
+                                micros_to_ms(micros),
+                                format_micros(limit.micros)
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    violations
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_micros() {
+        assert_eq!(parse_duration_micros("1ms"), Ok(1_000));
+        assert_eq!(parse_duration_micros("500us"), Ok(500));
+        assert_eq!(parse_duration_micros("2s"), Ok(2_000_000));
+        assert_eq!(parse_duration_micros("1.5ms"), Ok(1_500));
+
+        assert!(parse_duration_micros("10").is_err());
+        assert!(parse_duration_micros("ms").is_err());
+        assert!(parse_duration_micros("10m").is_err());
+    }
+
+    #[test]
+    fn test_parse_budget_limit() {
+        let limit: BudgetLimit = "total=1s".parse().unwrap();
+        assert_eq!(limit.scope, BudgetScope::Total);
+        assert_eq!(limit.micros, 1_000_000);
+
+        let limit: BudgetLimit = "day05=10ms".parse().unwrap();
+        assert_eq!(limit.scope, BudgetScope::OneDay(5));
+        assert_eq!(limit.micros, 10_000);
+        assert_eq!(limit.to_string(), "day05 under 10ms");
+
+        assert!("day99=1ms".parse::<BudgetLimit>().is_err());
+        assert!("day00=1ms".parse::<BudgetLimit>().is_err());
+        assert!("week=1ms".parse::<BudgetLimit>().is_err());
+        assert!("1ms".parse::<BudgetLimit>().is_err());
+    }
+}
@@ -2,6 +2,13 @@ use anyhow::{Context, Result};
 use std::env;
 use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
+
+use super::analytics::micros_to_ms;
+use super::build;
+use super::config::BuildSettings;
+use super::process;
+use super::spans::render_span_tree;
 
 #[derive(Debug, Clone, Copy)]
 pub enum TimePart {
@@ -20,7 +27,7 @@ impl TimePart {
     }
 }
 
-fn find_workspace_root() -> Result<PathBuf> {
+pub(super) fn find_workspace_root() -> Result<PathBuf> {
     let mut current_dir = env::current_dir().context("Failed to get current directory")?;
 
     loop {
@@ -43,7 +50,7 @@ fn find_workspace_root() -> Result<PathBuf> {
     }
 }
 
-fn detect_current_day() -> Result<u8> {
+pub(super) fn detect_current_day() -> Result<u8> {
     let current_dir = env::current_dir().context("Failed to get current directory")?;
     let dir_name = current_dir
         .file_name()
@@ -65,7 +72,12 @@ fn detect_current_day() -> Result<u8> {
     )
 }
 
-pub fn time_day(day: Option<u8>, part: TimePart) -> Result<()> {
+pub fn time_day(
+    day: Option<u8>,
+    part: TimePart,
+    timeout: Option<Duration>,
+    spans: bool,
+) -> Result<()> {
     // Determine the day number (from parameter or auto-detect)
     let day = match day {
         Some(d) => {
@@ -87,21 +99,64 @@ pub fn time_day(day: Option<u8>, part: TimePart) -> Result<()> {
         anyhow::bail!("Day {day} does not exist. Run 'aoc-cli add {day}' first.");
     }
 
-    println!("Running {day_name} with timing ({part:?})...\n");
+    let settings = BuildSettings::load(&workspace_root)?;
+
+    println!("Building {day_name} ({settings})...\n");
+
+    let build = build::build_package(&workspace_root, settings, &day_name)?;
+
+    if let Some(errors) = build.errors_for(&day_name) {
+        anyhow::bail!("Failed to compile {day_name}:\n{}", errors.join("\n"));
+    }
+
+    let binary = build
+        .binary(&day_name)
+        .context(format!("Failed to build {day_name}"))?;
+
+    println!("\nRunning {day_name} with timing ({part:?})...\n");
+
+    let mut command = Command::new(binary);
+    command.arg(part.flag()).current_dir(&day_path);
+
+    if spans {
+        command.arg("--spans");
+    }
 
-    let output = Command::new("cargo")
-        .current_dir(&workspace_root)
-        .args(["run", "--release", "-p", &day_name, "--", part.flag()])
-        .output()
+    let output = process::run_measured(&mut command, timeout)
         .context(format!("Failed to run {day_name}"))?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Failed to run {day_name}:\n{stderr}");
+    for line in output.stdout.lines() {
+        if !line.starts_with("SPAN:") {
+            println!("{line}");
+        }
+    }
+
+    if output.timed_out() {
+        anyhow::bail!(
+            "{day_name} timed out after {:.1}s",
+            timeout.unwrap_or_default().as_secs_f64()
+        );
+    }
+
+    if !output.success() {
+        anyhow::bail!("Failed to run {day_name}:\n{}", output.stderr);
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    print!("{stdout}");
+    if let Some(usage) = output.usage {
+        #[allow(clippy::cast_precision_loss)]
+        let peak_rss_mb = usage.peak_rss_kb as f64 / 1024.0;
+        println!(
+            "\nPeak memory: {peak_rss_mb:.1}MB | CPU time: {:.3}ms",
+            micros_to_ms(usage.cpu_micros)
+        );
+    }
+
+    if spans {
+        match render_span_tree(&output.stdout) {
+            Some(tree) => print!("\n{tree}"),
+            None => println!("\nNo spans were recorded. Is utils up to date?"),
+        }
+    }
 
     Ok(())
 }
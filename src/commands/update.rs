@@ -1,10 +1,15 @@
 use anyhow::{Context, Result};
+use std::fmt::Write;
 use std::fs;
 use std::path::Path;
 
+use super::config;
+use super::runner;
 use crate::templates;
 
-pub fn update_utils() -> Result<()> {
+/// Updates the utils crate. Day crates that need changes to keep compiling are
+/// listed, and only rewritten with `migrate`.
+pub fn update_utils(migrate: bool) -> Result<()> {
     if !Path::new("Cargo.toml").exists() {
         anyhow::bail!("Not in a workspace directory. Run 'aoc-cli init <name>' first.");
     }
@@ -22,6 +27,18 @@ pub fn update_utils() -> Result<()> {
         ("utils/src/directions.rs", templates::UTILS_DIRECTIONS_RS),
         ("utils/src/points.rs", templates::UTILS_POINTS_RS),
         ("utils/src/point3d.rs", templates::UTILS_POINT3D_RS),
+        ("utils/src/spans.rs", templates::UTILS_SPANS_RS),
+        ("utils/src/error.rs", templates::UTILS_ERROR_RS),
+        ("utils/src/timer.rs", templates::UTILS_TIMER_RS),
+        ("utils/src/params.rs", templates::UTILS_PARAMS_RS),
+        ("utils/src/testing.rs", templates::UTILS_TESTING_RS),
+        ("utils/src/visualize.rs", templates::UTILS_VISUALIZE_RS),
+        ("utils/src/registry.rs", templates::UTILS_REGISTRY_RS),
+        ("utils/src/debug.rs", templates::UTILS_DEBUG_RS),
+        ("utils/src/panics.rs", templates::UTILS_PANICS_RS),
+        ("utils/src/grid.rs", templates::UTILS_GRID_RS),
+        ("utils/src/sparse_grid.rs", templates::UTILS_SPARSE_GRID_RS),
+        ("utils/src/hex.rs", templates::UTILS_HEX_RS),
     ];
 
     for (path, content) in &files {
@@ -29,8 +46,364 @@ pub fn update_utils() -> Result<()> {
         println!("Updated {path}");
     }
 
+    migrate_days(migrate)?;
+    config::ensure_bench_profile(Path::new("."))?;
+
     println!("\nUtils crate updated successfully!");
     println!("You may need to rebuild your project: cargo build --release");
 
     Ok(())
 }
+
+/// A day's `main.rs` written against an older version of the `Solution` trait, or
+/// still holding the solution, and what it becomes.
+struct DayMigration {
+    path: String,
+    content: String,
+    /// The new `lib.rs`, when the solution moves out of `main.rs`.
+    lib: Option<(String, String)>,
+    changes: Vec<String>,
+}
+
+/// Lists the day crates that need changes to keep compiling after the update, or to
+/// be linked into the workspace runner. With `apply`, rewrites them and keeps each
+/// original `main.rs` as `main.rs.bak`.
+fn migrate_days(apply: bool) -> Result<()> {
+    let migrations: Vec<DayMigration> = (1..=25).filter_map(plan_migration).collect();
+
+    if !migrations.is_empty() {
+        println!("\nDay crates written for an older version of utils:");
+        for migration in &migrations {
+            println!("  {}", migration.path);
+            for change in &migration.changes {
+                println!("    - {change}");
+            }
+        }
+    }
+
+    if !apply {
+        if !migrations.is_empty() {
+            println!(
+                "\nRun 'aoc-cli update utils --migrate' to make these changes; the original \
+                 main.rs files are kept as main.rs.bak."
+            );
+        }
+        return runner::sync_runner(Path::new("."));
+    }
+
+    for migration in migrations {
+        let backup = format!("{}.bak", migration.path);
+        if !Path::new(&backup).exists() {
+            fs::copy(&migration.path, &backup).context(format!("Failed to write {backup}"))?;
+        }
+
+        if let Some((lib_path, lib)) = &migration.lib {
+            fs::write(lib_path, lib).context(format!("Failed to write {lib_path}"))?;
+        }
+        fs::write(&migration.path, &migration.content)
+            .context(format!("Failed to write {}", migration.path))?;
+
+        println!("Migrated {} (backup in {backup})", migration.path);
+    }
+
+    runner::sync_runner(Path::new("."))
+}
+
+fn plan_migration(day: u8) -> Option<DayMigration> {
+    let path = format!("day{day:02}/src/main.rs");
+    let mut content = fs::read_to_string(&path).ok()?;
+    let mut changes = Vec::new();
+
+    if let Some(migrated) = migrate_outputs(&content) {
+        content = migrated;
+        changes.push("`type Output` becomes `type Output1`/`type Output2`".to_string());
+    }
+
+    if let Some(migrated) = add_params(&content) {
+        content = migrated;
+        changes.push("`type Params = ();` is added".to_string());
+    }
+
+    let mut lib = None;
+    let lib_path = format!("day{day:02}/src/lib.rs");
+    if !Path::new(&lib_path).exists() {
+        if let Some((lib_content, main)) = split_main(&content, day) {
+            content = main;
+            changes.push(format!(
+                "the solution moves to {lib_path}, so that 'run --all' runs it"
+            ));
+            lib = Some((lib_path, lib_content));
+        } else if content.contains("run_solution!") {
+            println!(
+                "Could not move the solution in {path} to lib.rs; it will not be part of 'run --all'"
+            );
+        }
+    }
+
+    (!changes.is_empty()).then_some(DayMigration {
+        path,
+        content,
+        lib,
+        changes,
+    })
+}
+
+/// Splits a day's `main.rs` from before day crates had a library into the solution
+/// (`lib.rs`, registered for the runner) and a `main.rs` that only runs it.
+fn split_main(content: &str, day: u8) -> Option<(String, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|line| *line == "fn main() {")?;
+    let end = start + lines[start..].iter().position(|line| *line == "}")?;
+
+    // Only the generated `fn main() { run_solution!(DayXX); }` is moved automatically
+    let [body] = &lines[start + 1..end] else {
+        return None;
+    };
+    let solution = body
+        .trim()
+        .strip_prefix("run_solution!(")?
+        .strip_suffix(");")?;
+    if !solution.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+
+    // `main.rs` refers to the solution from outside the library, so it must become `pub`
+    let declaration = format!("struct {solution};");
+    if !lines.contains(&declaration.as_str()) {
+        return None;
+    }
+
+    let mut lib = String::with_capacity(content.len());
+    for (index, line) in lines.iter().enumerate() {
+        if index == start {
+            let _ = writeln!(lib, "utils::register_day!({solution});");
+        } else if (start..=end).contains(&index) {
+            // The rest of `fn main`
+        } else if line.starts_with("use utils::") {
+            let line = line
+                .replace("run_solution, ", "")
+                .replace(", run_solution", "");
+            if line != "use utils::run_solution;" {
+                let _ = writeln!(lib, "{line}");
+            }
+        } else if *line == declaration {
+            let _ = writeln!(lib, "pub {line}");
+        } else {
+            let _ = writeln!(lib, "{line}");
+        }
+    }
+
+    let main = templates::DAY_MAIN_RS
+        .replace("Day{DAY}", solution)
+        .replace("{DAY}", &format!("{day:02}"));
+
+    Some((lib, main))
+}
+
+fn migrate_outputs(content: &str) -> Option<String> {
+    if !content.contains("type Output =") || content.contains("type Output1") {
+        return None;
+    }
+
+    let mut migrated = String::with_capacity(content.len());
+    let mut part = "1";
+
+    for line in content.lines() {
+        if let Some(ty) = line.trim().strip_prefix("type Output =") {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let ty = ty.trim();
+            let _ = writeln!(migrated, "{indent}type Output1 = {ty}");
+            let _ = writeln!(migrated, "{indent}type Output2 = {ty}");
+            continue;
+        }
+
+        // `Self::Output` belongs to whichever part's function it appears in
+        if line.contains("part1(") {
+            part = "1";
+        } else if line.contains("part2(") {
+            part = "2";
+        }
+
+        migrated.push_str(&replace_output(line, part));
+        migrated.push('\n');
+    }
+
+    Some(migrated)
+}
+
+fn replace_output(line: &str, part: &str) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(index) = rest.find("Self::Output") {
+        let end = index + "Self::Output".len();
+        result.push_str(&rest[..end]);
+
+        if !rest[end..].starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+            result.push_str(part);
+        }
+
+        rest = &rest[end..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+fn add_params(content: &str) -> Option<String> {
+    if content.contains("type Params") {
+        return None;
+    }
+
+    let mut migrated = String::with_capacity(content.len());
+    let mut added = false;
+
+    for line in content.lines() {
+        migrated.push_str(line);
+        migrated.push('\n');
+
+        if !added && line.trim().starts_with("type Output2 =") {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            let _ = writeln!(migrated, "{indent}type Params = ();");
+            added = true;
+        }
+    }
+
+    added.then_some(migrated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_main() {
+        let main = "\
+use utils::{Solution, run_solution};
+
+struct Day05;
+
+const LIMIT: usize = 3;
+
+impl Solution for Day05 {}
+
+fn main() {
+    run_solution!(Day05);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+";
+        let (lib, main) = split_main(main, 5).unwrap();
+
+        assert_eq!(
+            lib,
+            "\
+use utils::{Solution};
+
+pub struct Day05;
+
+const LIMIT: usize = 3;
+
+impl Solution for Day05 {}
+
+utils::register_day!(Day05);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+}
+"
+        );
+        assert_eq!(
+            main,
+            "fn main() {\n    utils::run_solution!(day05::Day05);\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_split_main_leaves_other_mains() {
+        // Already migrated
+        let migrated = templates::DAY_MAIN_RS.replace("{DAY}", "05");
+        assert_eq!(split_main(&migrated, 5), None);
+
+        // `main` does more than run the solution
+        let custom = "struct Day05;\n\nfn main() {\n    setup();\n    run_solution!(Day05);\n}\n";
+        assert_eq!(split_main(custom, 5), None);
+
+        // The solution is not declared as a plain unit struct
+        let generic = "struct Day05<T>(T);\n\nfn main() {\n    run_solution!(Day05);\n}\n";
+        assert_eq!(split_main(generic, 5), None);
+    }
+
+    #[test]
+    fn test_replace_output() {
+        assert_eq!(
+            replace_output("fn part1(&self) -> Self::Output {", "1"),
+            "fn part1(&self) -> Self::Output1 {"
+        );
+        assert_eq!(
+            replace_output("Result<Self::Output, SolutionError>", "2"),
+            "Result<Self::Output2, SolutionError>"
+        );
+        assert_eq!(
+            replace_output("(Self::Output, Self::Output)", "1"),
+            "(Self::Output1, Self::Output1)"
+        );
+        assert_eq!(replace_output("Self::Output1", "2"), "Self::Output1");
+        assert_eq!(replace_output("Self::Output_", "2"), "Self::Output_");
+        assert_eq!(replace_output("let output = 1;", "1"), "let output = 1;");
+    }
+
+    #[test]
+    fn test_migrate_outputs() {
+        let old = "\
+impl Solution for Day01 {
+    type Output = u64;
+
+    fn part1(&self, input: &Self::Input) -> Self::Output {
+        0
+    }
+
+    fn try_part2(&self, input: &Self::Input) -> Result<Self::Output, SolutionError> {
+        Ok(0)
+    }
+}
+";
+        assert_eq!(
+            migrate_outputs(old).unwrap(),
+            "\
+impl Solution for Day01 {
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        0
+    }
+
+    fn try_part2(&self, input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Ok(0)
+    }
+}
+"
+        );
+
+        assert_eq!(
+            migrate_outputs("type Output1 = u64;\ntype Output2 = u64;\n"),
+            None
+        );
+        assert_eq!(migrate_outputs("struct Day01;\n"), None);
+    }
+
+    #[test]
+    fn test_add_params() {
+        assert_eq!(
+            add_params("    type Output1 = u64;\n    type Output2 = u64;\n").unwrap(),
+            "    type Output1 = u64;\n    type Output2 = u64;\n    type Params = ();\n"
+        );
+        assert_eq!(add_params("type Output2 = u64;\ntype Params = ();\n"), None);
+        assert_eq!(add_params("struct Day01;\n"), None);
+    }
+}
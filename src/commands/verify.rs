@@ -0,0 +1,193 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use super::analytics::{PartPanic, parse_panics};
+use super::answers::{Answers, Check, PartOutcome};
+use super::build;
+use super::config::BuildSettings;
+use super::process;
+use super::time::find_workspace_root;
+
+struct DayReport {
+    day: u8,
+    outcome: Outcome,
+}
+
+enum Outcome {
+    /// The day printed at least one answer. If it exited with an error, `stderr`
+    /// holds its report and `panics` the parts that panicked.
+    Ran {
+        expected: Option<Answers>,
+        actual: Answers,
+        panics: Vec<PartPanic>,
+        stderr: Option<String>,
+    },
+    BuildFailed,
+    RunFailed(String),
+}
+
+impl DayReport {
+    fn parts(&self) -> Option<[PartOutcome; 2]> {
+        let Outcome::Ran {
+            expected,
+            actual,
+            panics,
+            stderr,
+        } = &self.outcome
+        else {
+            return None;
+        };
+        let no_answers = Answers::default();
+        let expected = expected.as_ref().unwrap_or(&no_answers);
+        let failed = stderr.is_some();
+
+        Some([
+            PartOutcome::of(
+                1,
+                expected.part1.as_deref(),
+                actual.part1.as_deref(),
+                panics,
+                failed,
+            ),
+            PartOutcome::of(
+                2,
+                expected.part2.as_deref(),
+                actual.part2.as_deref(),
+                panics,
+                failed,
+            ),
+        ])
+    }
+
+    fn passed(&self) -> bool {
+        self.parts()
+            .is_some_and(|parts| parts.iter().all(|part| part.passed()))
+    }
+}
+
+/// Runs every day and checks its answers against the ones recorded in
+/// `dayXX/answers.txt`, printing a pass/fail/missing matrix.
+///
+/// # Errors
+///
+/// Fails if any day does not build, does not run, or produces a wrong answer.
+/// Days or parts without a recorded answer are reported as missing but do not fail.
+pub fn verify_answers() -> Result<()> {
+    let workspace_root = find_workspace_root()?;
+
+    let days: Vec<u8> = (1..=25)
+        .filter(|day| workspace_root.join(format!("day{day:02}")).exists())
+        .collect();
+
+    if days.is_empty() {
+        anyhow::bail!("No day crates found");
+    }
+
+    let settings = BuildSettings::load(&workspace_root)?;
+
+    println!("Building all day crates ({settings})...\n");
+
+    let build = build::build_workspace(&workspace_root, settings)?;
+
+    println!("\nVerifying answers...\n");
+
+    let mut reports = Vec::new();
+
+    for day in days {
+        let day_name = format!("day{day:02}");
+        let day_path = workspace_root.join(&day_name);
+
+        let Some(binary) = build
+            .binary(&day_name)
+            .filter(|_| build.errors_for(&day_name).is_none())
+        else {
+            reports.push(DayReport {
+                day,
+                outcome: Outcome::BuildFailed,
+            });
+            continue;
+        };
+
+        let output = process::run_measured(Command::new(binary).current_dir(&day_path), None)
+            .context(format!("Failed to run {day_name}"))?;
+
+        let actual = Answers::from_output(&output.stdout);
+
+        // A panic in one part does not stop the other, whose answer is still checked
+        let outcome = if !output.success() && actual.part1.is_none() && actual.part2.is_none() {
+            Outcome::RunFailed(output.stderr.trim().to_string())
+        } else {
+            Outcome::Ran {
+                expected: Answers::load(&day_path.join("answers.txt"))?,
+                actual,
+                panics: parse_panics(&output.stdout),
+                stderr: (!output.success()).then(|| output.stderr.trim().to_string()),
+            }
+        };
+
+        reports.push(DayReport { day, outcome });
+    }
+
+    print_matrix(&reports);
+
+    let failures = reports.iter().filter(|report| !report.passed()).count();
+    if failures > 0 {
+        anyhow::bail!("{failures} day(s) failed verification");
+    }
+
+    println!("\nAll recorded answers match");
+
+    Ok(())
+}
+
+fn print_matrix(reports: &[DayReport]) {
+    println!("| day | part 1   | part 2   |");
+    println!("| --- | -------- | -------- |");
+
+    for report in reports {
+        let (part1, part2) = match (&report.outcome, report.parts()) {
+            (_, Some([part1, part2])) => (part1.label(), part2.label()),
+            (Outcome::BuildFailed, None) => ("build", "build"),
+            _ => ("crashed", "crashed"),
+        };
+
+        println!("| {:>3} | {part1:<8} | {part2:<8} |", report.day);
+    }
+
+    for report in reports {
+        match &report.outcome {
+            Outcome::Ran {
+                expected,
+                actual,
+                stderr,
+                ..
+            } => {
+                let no_answers = Answers::default();
+                let expected = expected.as_ref().unwrap_or(&no_answers);
+                let parts = report.parts().unwrap_or([PartOutcome::Crashed; 2]);
+
+                for (part, expected, actual, outcome) in [
+                    (1, &expected.part1, &actual.part1, parts[0]),
+                    (2, &expected.part2, &actual.part2, parts[1]),
+                ] {
+                    if outcome == PartOutcome::Checked(Check::Fail) {
+                        println!(
+                            "\nDay {} part {part}: expected {}, got {}",
+                            report.day,
+                            expected.as_deref().unwrap_or_default(),
+                            actual.as_deref().unwrap_or("no answer")
+                        );
+                    }
+                }
+
+                if let Some(stderr) = stderr {
+                    println!("\nDay {} failed:\n{stderr}", report.day);
+                }
+            }
+            Outcome::RunFailed(stderr) => {
+                println!("\nDay {} failed to run:\n{stderr}", report.day);
+            }
+            Outcome::BuildFailed => {}
+        }
+    }
+}
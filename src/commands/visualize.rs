@@ -0,0 +1,617 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use super::build;
+use super::config::BuildSettings;
+use super::process;
+use super::time::{detect_current_day, find_workspace_root};
+
+const MIN_FPS: f64 = 0.25;
+const MAX_FPS: f64 = 240.0;
+// Size of one grid cell in exported images, in pixels
+const PPM_CELL: usize = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameKind {
+    Text,
+    Grid,
+}
+
+/// One frame emitted by `utils::frame_text` or `utils::frame_grid`.
+#[derive(Debug)]
+struct Frame {
+    kind: FrameKind,
+    title: String,
+    lines: Vec<String>,
+    /// Highlighted `(x, y)` cells of a grid frame.
+    highlights: HashSet<(usize, usize)>,
+}
+
+/// Runs a day with `--visualize` and plays the frames it emits in the terminal while
+/// it runs, or writes them to `export` (a text log, or a numbered PPM sequence for
+/// `.ppm`). When stdout is not a terminal, the frames are printed as a text log.
+pub fn visualize_day(day: Option<u8>, export: Option<&Path>, fps: f64) -> Result<()> {
+    let day = match day {
+        Some(d) => {
+            if !(1..=25).contains(&d) {
+                anyhow::bail!("Day must be between 1 and 25");
+            }
+            d
+        }
+        None => detect_current_day()?,
+    };
+
+    if !(MIN_FPS..=MAX_FPS).contains(&fps) {
+        anyhow::bail!("--fps must be between {MIN_FPS} and {MAX_FPS}");
+    }
+
+    let workspace_root = find_workspace_root()?;
+
+    let day_name = format!("day{day:02}");
+    let day_path = workspace_root.join(&day_name);
+
+    if !day_path.exists() {
+        anyhow::bail!("Day {day} does not exist. Run 'aoc-cli add {day}' first.");
+    }
+
+    let settings = BuildSettings::load(&workspace_root)?;
+
+    println!("Building {day_name} ({settings})...\n");
+
+    let build = build::build_package(&workspace_root, settings, &day_name)?;
+
+    if let Some(errors) = build.errors_for(&day_name) {
+        anyhow::bail!("Failed to compile {day_name}:\n{}", errors.join("\n"));
+    }
+
+    let binary = build
+        .binary(&day_name)
+        .context(format!("Failed to build {day_name}"))?;
+
+    println!("\nRunning {day_name} with visualization...\n");
+
+    // The day's input must not compete with playback for key presses
+    let mut child = Command::new(binary)
+        .arg("--visualize")
+        .current_dir(&day_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(format!("Failed to run {day_name}"))?;
+
+    let stderr = process::capture(child.stderr.take());
+    let stdout = child
+        .stdout
+        .take()
+        .context(format!("Failed to capture the output of {day_name}"))?;
+
+    // Frames are parsed as the day prints them, so playback starts right away
+    let (sender, receiver) = mpsc::channel();
+    let reader = thread::spawn(move || {
+        let mut parser = FrameParser::default();
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Some(frame) = parser.push_line(&line) {
+                // Playback may have been quit already
+                let _ = sender.send(frame);
+            }
+        }
+        let (last, rest) = parser.finish();
+        if let Some(frame) = last {
+            let _ = sender.send(frame);
+        }
+        rest
+    });
+
+    let frame_count = match export {
+        Some(path) => {
+            let frames: Vec<Frame> = receiver.iter().collect();
+            if frames.is_empty() {
+                // Reported below
+            } else if path.extension().is_some_and(|ext| ext == "ppm") {
+                export_ppm(&frames, path)?;
+            } else {
+                fs::write(path, text_log(&frames))
+                    .context(format!("Failed to write {}", path.display()))?;
+                println!("Wrote {} frame(s) to {}", frames.len(), path.display());
+            }
+            frames.len()
+        }
+        None if io::stdout().is_terminal() => play(&receiver, fps)?,
+        None => print_frames(&receiver)?,
+    };
+
+    // Quitting playback early stops the day too
+    let stopped = child
+        .try_wait()
+        .context(format!("Failed to wait for {day_name}"))?
+        .is_none();
+    if stopped {
+        let _ = child.kill();
+    }
+    let status = child
+        .wait()
+        .context(format!("Failed to wait for {day_name}"))?;
+    let rest = reader.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+
+    if frame_count == 0 {
+        println!("{}", rest.trim_end());
+        anyhow::bail!(
+            "{day_name} emitted no frames. Call utils::frame_grid or utils::frame_text \
+             (run 'aoc-cli update utils' if they are missing)."
+        );
+    }
+
+    println!("{}", rest.trim_end());
+
+    if !stopped && !status.success() {
+        anyhow::bail!("{day_name} failed:\n{}", stderr.trim_end());
+    }
+
+    Ok(())
+}
+
+/// Splits the frames out of a run's stdout, one line at a time, keeping the rest of
+/// the output.
+#[derive(Default)]
+struct FrameParser {
+    current: Option<Frame>,
+    rest: String,
+}
+
+impl FrameParser {
+    /// Takes the next line, returning the frame it completes.
+    fn push_line(&mut self, line: &str) -> Option<Frame> {
+        if let Some(frame) = self.current.as_mut() {
+            if line == "FRAME_END" {
+                return self.current.take();
+            } else if let Some(text) = line.strip_prefix('|') {
+                frame.lines.push(text.to_string());
+            } else if let Some(cells) = line.strip_prefix("HIGHLIGHT:") {
+                frame
+                    .highlights
+                    .extend(cells.split_whitespace().filter_map(|cell| {
+                        let (x, y) = cell.split_once(',')?;
+                        Some((x.parse().ok()?, y.parse().ok()?))
+                    }));
+            }
+        } else if let Some(header) = line.strip_prefix("FRAME:") {
+            let (kind, title) = header.split_once(':').unwrap_or((header, ""));
+            self.current = Some(Frame {
+                kind: if kind == "grid" {
+                    FrameKind::Grid
+                } else {
+                    FrameKind::Text
+                },
+                title: title.to_string(),
+                lines: Vec::new(),
+                highlights: HashSet::new(),
+            });
+        } else {
+            self.rest.push_str(line);
+            self.rest.push('\n');
+        }
+
+        None
+    }
+
+    /// The unfinished frame of a run that crashed mid-frame, which still shows what
+    /// it got to, and the output that was not part of a frame.
+    fn finish(self) -> (Option<Frame>, String) {
+        (self.current, self.rest)
+    }
+}
+
+fn text_log(frames: &[Frame]) -> String {
+    let mut log = String::new();
+
+    for (index, frame) in frames.iter().enumerate() {
+        let number = format!("{}/{}", index + 1, frames.len());
+        write_log_entry(&mut log, &number, frame);
+    }
+
+    log
+}
+
+fn write_log_entry(log: &mut String, number: &str, frame: &Frame) {
+    let _ = writeln!(log, "=== frame {number}: {} ===", frame.title);
+
+    if !frame.highlights.is_empty() {
+        let mut cells: Vec<_> = frame.highlights.iter().collect();
+        cells.sort_unstable_by_key(|(x, y)| (*y, *x));
+        let cells: Vec<String> = cells.iter().map(|(x, y)| format!("({x}, {y})")).collect();
+        let _ = writeln!(log, "highlighted: {}", cells.join(" "));
+    }
+
+    for line in &frame.lines {
+        let _ = writeln!(log, "{line}");
+    }
+    log.push('\n');
+}
+
+/// Prints the frames as a text log as they arrive, for when stdout is not a
+/// terminal. Returns how many there were.
+fn print_frames(frames: &Receiver<Frame>) -> Result<usize> {
+    let mut count = 0;
+
+    for frame in frames {
+        count += 1;
+        let mut entry = String::new();
+        write_log_entry(&mut entry, &count.to_string(), &frame);
+
+        let mut stdout = io::stdout().lock();
+        stdout
+            .write_all(entry.as_bytes())
+            .and_then(|()| stdout.flush())
+            .context("Failed to write frame")?;
+    }
+
+    Ok(count)
+}
+
+/// Writes every grid frame as `<stem>-0001.ppm`, `<stem>-0002.ppm`, ... next to `path`.
+fn export_ppm(frames: &[Frame], path: &Path) -> Result<()> {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("frame");
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+
+    let grids: Vec<&Frame> = frames
+        .iter()
+        .filter(|frame| frame.kind == FrameKind::Grid)
+        .collect();
+
+    for (index, frame) in grids.iter().enumerate() {
+        let file = directory.join(format!("{stem}-{:04}.ppm", index + 1));
+        fs::write(&file, render_ppm(frame))
+            .context(format!("Failed to write {}", file.display()))?;
+    }
+
+    println!(
+        "Wrote {} image(s) to {}",
+        grids.len(),
+        directory.join(format!("{stem}-*.ppm")).display()
+    );
+
+    let skipped = frames.len() - grids.len();
+    if skipped > 0 {
+        println!("Skipped {skipped} text frame(s), which have no image form");
+    }
+
+    Ok(())
+}
+
+fn render_ppm(frame: &Frame) -> Vec<u8> {
+    let columns = frame
+        .lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let rows = frame.lines.len().max(1);
+    let (width, height) = (columns * PPM_CELL, rows * PPM_CELL);
+
+    let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+    image.reserve(width * height * 3);
+
+    for y in 0..rows {
+        let line: Vec<char> = frame
+            .lines
+            .get(y)
+            .map(|line| line.chars().collect())
+            .unwrap_or_default();
+
+        let colors: Vec<[u8; 3]> = (0..columns)
+            .map(|x| {
+                if frame.highlights.contains(&(x, y)) {
+                    [220, 50, 47]
+                } else {
+                    cell_color(line.get(x).copied().unwrap_or(' '))
+                }
+            })
+            .collect();
+
+        for _ in 0..PPM_CELL {
+            for color in &colors {
+                for _ in 0..PPM_CELL {
+                    image.extend_from_slice(color);
+                }
+            }
+        }
+    }
+
+    image
+}
+
+/// Walls dark, floor light, and every other character its own color.
+fn cell_color(glyph: char) -> [u8; 3] {
+    const PALETTE: [[u8; 3]; 8] = [
+        [78, 121, 167],
+        [242, 142, 43],
+        [89, 161, 79],
+        [237, 201, 72],
+        [176, 122, 161],
+        [118, 183, 178],
+        [156, 117, 95],
+        [255, 157, 167],
+    ];
+
+    match glyph {
+        '#' => [40, 40, 40],
+        '.' => [235, 235, 235],
+        ' ' => [255, 255, 255],
+        other => PALETTE[other as usize % PALETTE.len()],
+    }
+}
+
+enum Key {
+    TogglePause,
+    Next,
+    Previous,
+    Faster,
+    Slower,
+    Quit,
+    Other,
+}
+
+/// Plays the frames in the terminal as the day emits them. Returns how many there
+/// were; without any, the screen is left alone.
+fn play(receiver: &Receiver<Frame>, mut fps: f64) -> Result<usize> {
+    let Ok(first) = receiver.recv() else {
+        return Ok(0);
+    };
+    let mut frames = vec![first];
+    // Whether the day is still running, so more frames may come
+    let mut running = true;
+
+    let terminal = Terminal::enter();
+    let mut index = 0;
+    let mut paused = false;
+
+    loop {
+        loop {
+            match receiver.try_recv() {
+                Ok(frame) => frames.push(frame),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    running = false;
+                    break;
+                }
+            }
+        }
+
+        let at_end = index + 1 == frames.len();
+        let total = if running {
+            format!("{}+", frames.len())
+        } else {
+            frames.len().to_string()
+        };
+        draw(
+            &frames[index],
+            index,
+            &total,
+            paused || (at_end && !running),
+            fps,
+        )?;
+
+        // Without a terminal to read keys from, play once and stop
+        if !terminal.interactive {
+            if at_end && !running {
+                break;
+            }
+            thread::sleep(Duration::from_secs_f64(1.0 / fps));
+            if !at_end {
+                index += 1;
+            }
+            continue;
+        }
+
+        // While the day runs, the screen is redrawn to pick up its new frames
+        let timeout = (running || (!paused && !at_end)).then(|| Duration::from_secs_f64(1.0 / fps));
+
+        match read_key(timeout)? {
+            // Only a timeout of running playback advances
+            None if !paused && !at_end => index += 1,
+            None => {}
+            Some(Key::TogglePause) => paused = !paused,
+            Some(Key::Next) => {
+                paused = true;
+                index = (index + 1).min(frames.len() - 1);
+            }
+            Some(Key::Previous) => {
+                paused = true;
+                index = index.saturating_sub(1);
+            }
+            Some(Key::Faster) => fps = (fps * 2.0).min(MAX_FPS),
+            Some(Key::Slower) => fps = (fps / 2.0).max(MIN_FPS),
+            Some(Key::Quit) => break,
+            Some(Key::Other) => {}
+        }
+    }
+
+    drop(terminal);
+    Ok(frames.len())
+}
+
+fn draw(frame: &Frame, index: usize, total: &str, paused: bool, fps: f64) -> Result<()> {
+    // Move to the top-left corner and clear, to avoid flicker from scrolling
+    let mut screen = String::from("\x1b[H\x1b[2J");
+
+    let _ = write!(screen, "frame {}/{total}", index + 1);
+    if !frame.title.is_empty() {
+        let _ = write!(screen, "  {}", frame.title);
+    }
+    screen.push_str("\r\n\r\n");
+
+    for (y, line) in frame.lines.iter().enumerate() {
+        for (x, glyph) in line.chars().enumerate() {
+            if frame.highlights.contains(&(x, y)) {
+                let _ = write!(screen, "\x1b[7m{glyph}\x1b[0m");
+            } else {
+                screen.push(glyph);
+            }
+        }
+        screen.push_str("\r\n");
+    }
+
+    let state = if paused { "paused" } else { "playing" };
+    let _ = write!(
+        screen,
+        "\r\n[{state}, {fps} fps]  space: pause  n/→: next  b/←: back  +/-: speed  q: quit"
+    );
+
+    let mut stdout = io::stdout().lock();
+    stdout
+        .write_all(screen.as_bytes())
+        .and_then(|()| stdout.flush())
+        .context("Failed to draw frame")
+}
+
+/// The terminal in playback mode: alternate screen, hidden cursor, and on unix
+/// unbuffered key input. Everything is restored on drop.
+struct Terminal {
+    interactive: bool,
+    #[cfg(unix)]
+    original: Option<libc::termios>,
+}
+
+impl Terminal {
+    #[cfg(unix)]
+    fn enter() -> Self {
+        // SAFETY: isatty only inspects the file descriptor
+        let is_tty = unsafe { libc::isatty(libc::STDIN_FILENO) } == 1;
+        let mut original = None;
+
+        if is_tty {
+            // SAFETY: `termios` is a plain C struct for which all-zero bytes are valid,
+            // and tcgetattr fully initializes it on success
+            let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+            if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &raw mut termios) } == 0 {
+                original = Some(termios);
+
+                // Read keys as they are pressed, without echo; Ctrl+C arrives as a key
+                termios.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+                termios.c_cc[libc::VMIN] = 1;
+                termios.c_cc[libc::VTIME] = 0;
+                // SAFETY: `termios` is a valid, initialized settings struct
+                unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw const termios) };
+            }
+        }
+
+        print!("\x1b[?1049h\x1b[?25l");
+
+        Self {
+            interactive: original.is_some(),
+            original,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn enter() -> Self {
+        print!("\x1b[?1049h\x1b[?25l");
+
+        Self { interactive: false }
+    }
+}
+
+/// Waits up to `timeout` (forever if `None`) for a key press.
+#[cfg(unix)]
+fn read_key(timeout: Option<Duration>) -> Result<Option<Key>> {
+    let Some(byte) = read_byte(timeout)? else {
+        return Ok(None);
+    };
+
+    let key = match byte {
+        b' ' | b'p' => Key::TogglePause,
+        b'n' | b'l' => Key::Next,
+        b'b' | b'h' => Key::Previous,
+        b'+' | b'=' => Key::Faster,
+        b'-' | b'_' => Key::Slower,
+        // Ctrl+C
+        b'q' | 3 => Key::Quit,
+        // Arrow keys arrive as ESC [ C (right) and ESC [ D (left)
+        0x1b => {
+            let short = Some(Duration::from_millis(10));
+            match (read_byte(short)?, read_byte(short)?) {
+                (Some(b'['), Some(b'C')) => Key::Next,
+                (Some(b'['), Some(b'D')) => Key::Previous,
+                (None, _) => Key::Quit,
+                _ => Key::Other,
+            }
+        }
+        _ => Key::Other,
+    };
+
+    Ok(Some(key))
+}
+
+#[cfg(not(unix))]
+fn read_key(_timeout: Option<Duration>) -> Result<Option<Key>> {
+    Ok(None)
+}
+
+#[cfg(unix)]
+fn read_byte(timeout: Option<Duration>) -> Result<Option<u8>> {
+    let mut poll = libc::pollfd {
+        fd: libc::STDIN_FILENO,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+
+    loop {
+        let timeout_ms = deadline.map_or(-1, |deadline| {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            i32::try_from(remaining.as_millis()).unwrap_or(i32::MAX)
+        });
+
+        // SAFETY: `poll` points to exactly one valid pollfd
+        let ready = unsafe { libc::poll(&raw mut poll, 1, timeout_ms) };
+        if ready > 0 {
+            break;
+        }
+        if ready == 0 {
+            return Ok(None);
+        }
+
+        // Interrupted by a signal (e.g. the terminal being resized): keep waiting
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err).context("Failed to wait for terminal input");
+        }
+    }
+
+    let mut byte = 0u8;
+    // SAFETY: reading one byte into a valid one-byte buffer
+    let read = unsafe { libc::read(libc::STDIN_FILENO, (&raw mut byte).cast(), 1) };
+
+    if read == 1 {
+        Ok(Some(byte))
+    } else {
+        anyhow::bail!("Failed to read from the terminal")
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+
+        #[cfg(unix)]
+        if let Some(original) = &self.original {
+            // SAFETY: restoring settings previously returned by tcgetattr
+            unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original) };
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use super::build;
+use super::config::BuildSettings;
+use super::process;
+use super::time::{detect_current_day, find_workspace_root};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Watches a day's sources, input and example files plus the utils crate, and
+/// rebuilds and reruns the day (optionally with its tests) on every change. A run
+/// that takes longer than `timeout` is killed, so the next change is still picked up.
+pub fn watch_day(day: Option<u8>, run_tests: bool, timeout: Duration) -> Result<()> {
+    let day = match day {
+        Some(d) => {
+            if !(1..=25).contains(&d) {
+                anyhow::bail!("Day must be between 1 and 25");
+            }
+            d
+        }
+        None => detect_current_day()?,
+    };
+
+    let workspace_root = find_workspace_root()?;
+
+    let day_name = format!("day{day:02}");
+    let day_path = workspace_root.join(&day_name);
+
+    if !day_path.exists() {
+        anyhow::bail!("Day {day} does not exist. Run 'aoc-cli add {day}' first.");
+    }
+
+    let watched = [
+        day_path.clone(),
+        workspace_root.join("utils/src"),
+        workspace_root.join("utils/Cargo.toml"),
+    ];
+
+    let mut snapshot = take_snapshot(&watched);
+
+    loop {
+        run_once(&workspace_root, &day_name, &day_path, run_tests, timeout)?;
+
+        println!("\nWatching {day_name} for changes (Ctrl+C to stop)...");
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+
+            let current = take_snapshot(&watched);
+            if current != snapshot {
+                // Editors often write files in several steps; let them settle
+                thread::sleep(DEBOUNCE);
+                snapshot = take_snapshot(&watched);
+                break;
+            }
+        }
+    }
+}
+
+fn run_once(
+    workspace_root: &Path,
+    day_name: &str,
+    day_path: &Path,
+    run_tests: bool,
+    timeout: Duration,
+) -> Result<()> {
+    // Clear the screen and move the cursor to the top-left corner
+    print!("\x1b[2J\x1b[H");
+    println!(
+        "[{}] Building {day_name}...\n",
+        chrono::Local::now().format("%H:%M:%S")
+    );
+
+    let settings = BuildSettings::load(workspace_root)?;
+    let build = match build::build_package(workspace_root, settings, day_name) {
+        Ok(build) => build,
+        Err(err) => {
+            println!("\nError: {err:#}");
+            return Ok(());
+        }
+    };
+
+    if !build.errors.is_empty() {
+        for (crate_name, errors) in &build.errors {
+            println!("\nCompile errors in {crate_name}:");
+            for error in errors {
+                println!("{}", error.trim_end());
+            }
+        }
+        return Ok(());
+    }
+
+    let Some(binary) = build.binary(day_name) else {
+        println!("\nFailed to build {day_name}");
+        return Ok(());
+    };
+
+    println!("\nRunning {day_name}...\n");
+
+    let output = process::run_measured(
+        Command::new(binary).arg("--timing").current_dir(day_path),
+        Some(timeout),
+    )
+    .context(format!("Failed to run {day_name}"))?;
+
+    for line in output.stdout.lines() {
+        // The machine-readable line is for analytics, not for humans
+        if !line.starts_with("TIMING") {
+            println!("{line}");
+        }
+    }
+
+    if output.timed_out() {
+        println!("\n{day_name} timed out after {}s", timeout.as_secs());
+    } else if !output.success() {
+        println!("\n{day_name} failed:\n{}", output.stderr.trim_end());
+    }
+
+    if run_tests {
+        run_day_tests(workspace_root, day_name, settings)?;
+    }
+
+    Ok(())
+}
+
+/// Runs the day's tests with the same profile and flags as its build, so they reuse
+/// the compiled dependencies.
+fn run_day_tests(workspace_root: &Path, day_name: &str, settings: BuildSettings) -> Result<()> {
+    println!("\nTesting {day_name}...\n");
+
+    let mut command = Command::new("cargo");
+    command
+        .current_dir(workspace_root)
+        .arg("test")
+        .args(settings.profile.cargo_args())
+        .args(["-p", day_name]);
+
+    if let Some(rustflags) = settings.rustflags() {
+        let existing = std::env::var("RUSTFLAGS").unwrap_or_default();
+        command.env("RUSTFLAGS", format!("{existing} {rustflags}").trim());
+    }
+
+    let output = command
+        .output()
+        .context(format!("Failed to run tests for {day_name}"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if output.status.success() {
+        let summary = stdout
+            .lines()
+            .find(|line| line.starts_with("test result:"))
+            .unwrap_or("test result: ok");
+        println!("{summary}");
+    } else if let Some(failures) = stdout.find("\nfailures:") {
+        println!("{}", stdout[failures..].trim());
+    } else {
+        // Tests did not get to run at all, most likely a compile error in test code
+        println!("{}", String::from_utf8_lossy(&output.stderr).trim_end());
+    }
+
+    Ok(())
+}
+
+fn take_snapshot(paths: &[PathBuf]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+
+    for path in paths {
+        collect_modified(path, &mut snapshot);
+    }
+
+    snapshot
+}
+
+fn collect_modified(path: &Path, snapshot: &mut Snapshot) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_dir() {
+        // Build output is not ours to watch, and would retrigger after every build
+        if path.file_name().is_some_and(|name| name == "target") {
+            return;
+        }
+
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                collect_modified(&entry.path(), snapshot);
+            }
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
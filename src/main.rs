@@ -1,5 +1,7 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::time::Duration;
 
 mod commands;
 mod templates;
@@ -24,10 +26,77 @@ enum Commands {
         #[arg(help = "Day number (1-25)")]
         day: u8,
     },
+    #[command(about = "Remove a day from the project")]
+    Remove {
+        #[arg(help = "Day number (1-25)")]
+        day: u8,
+        #[arg(
+            long,
+            help = "Remove the day even if it has a solution, input or answers"
+        )]
+        force: bool,
+    },
     #[command(about = "Run analytics on all day crates and output timing information")]
     Analytics {
-        #[arg(help = "Output file path", default_value = "analytics.md")]
-        file_path: String,
+        #[arg(help = "Output file path [default: analytics.<format extension>]")]
+        file_path: Option<String>,
+        #[arg(
+            long,
+            value_enum,
+            help = "Output format. Detected from the file extension if omitted, defaults to md"
+        )]
+        format: Option<commands::OutputFormat>,
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            help = "Kill a day that runs longer than this and record it as a timeout"
+        )]
+        timeout: Option<u64>,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            value_name = "SCOPE=DURATION",
+            help = "Fail if a budget is exceeded, e.g. total=1s, day=250ms, part=100ms or day05=10ms"
+        )]
+        budget: Vec<commands::BudgetLimit>,
+    },
+    #[command(about = "Show each day's timing trend across recorded analytics runs")]
+    History {
+        #[arg(help = "Only show this day (1-25)")]
+        day: Option<u8>,
+        #[arg(
+            long,
+            default_value_t = 10,
+            help = "Number of most recent runs to show"
+        )]
+        last: usize,
+    },
+    #[command(about = "Compare the latest analytics run against an earlier one")]
+    Compare {
+        #[arg(
+            long,
+            help = "Run number (as shown by 'history') or commit to compare against [default: previous run]"
+        )]
+        baseline: Option<String>,
+        #[arg(
+            long,
+            default_value_t = 10.0,
+            help = "Slowdown in percent above which a part is flagged as a regression"
+        )]
+        threshold: f64,
+    },
+    #[command(about = "Run a day and check its answers, optionally against every input")]
+    Run {
+        #[arg(help = "Day number (1-25). Auto-detected if in a day folder.")]
+        day: Option<u8>,
+        #[arg(long, help = "Also run dayXX/inputs/*.txt and inputs/<name>/dayXX.txt")]
+        all_inputs: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["day", "all_inputs"],
+            help = "Run every day in one process through the workspace runner"
+        )]
+        all: bool,
     },
     #[command(about = "Time a specific day's solution")]
     Time {
@@ -37,11 +106,97 @@ enum Commands {
         part1: bool,
         #[arg(long, help = "Time only part 2")]
         part2: bool,
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            help = "Kill the solution if it runs longer than this"
+        )]
+        timeout: Option<u64>,
+        #[arg(
+            long,
+            help = "Collect and show the utils::span timings inside the solution"
+        )]
+        spans: bool,
+    },
+    #[command(about = "Benchmark days against another git revision")]
+    Bench {
+        #[arg(long, help = "Git revision to compare against")]
+        baseline: String,
+        #[arg(help = "Day numbers (1-25). Defaults to the current day folder or every day.")]
+        days: Vec<u8>,
+        #[arg(
+            long,
+            default_value_t = 10,
+            help = "Number of runs of each build per day"
+        )]
+        iterations: usize,
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            default_value_t = 60,
+            help = "Count a run that takes longer than this as failed"
+        )]
+        timeout: u64,
+    },
+    #[command(about = "Rebuild and rerun a day every time its files or utils change")]
+    Watch {
+        #[arg(help = "Day number (1-25). Auto-detected if in a day folder.")]
+        day: Option<u8>,
+        #[arg(long, help = "Also run the day's tests after each run")]
+        test: bool,
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            default_value_t = 60,
+            help = "Kill a run that takes longer than this"
+        )]
+        timeout: u64,
+    },
+    #[command(about = "Play back the frames a day emits with utils::frame_grid/frame_text")]
+    Visualize {
+        #[arg(help = "Day number (1-25). Auto-detected if in a day folder.")]
+        day: Option<u8>,
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Write the frames to FILE instead of playing them: a text log, or a PPM sequence for .ppm"
+        )]
+        export: Option<PathBuf>,
+        #[arg(
+            long,
+            default_value_t = 10.0,
+            help = "Playback speed in frames per second"
+        )]
+        fps: f64,
+    },
+    #[command(about = "Check every day's answers against the recorded correct answers")]
+    Verify,
+    #[command(about = "Show or set the build profile used for timing runs")]
+    Profile {
+        #[arg(value_enum, help = "Build profile to use for timing runs")]
+        profile: Option<commands::BuildProfile>,
+        #[arg(
+            long,
+            overrides_with = "no_native",
+            help = "Build with -C target-cpu=native"
+        )]
+        native: bool,
+        #[arg(
+            long,
+            overrides_with = "native",
+            help = "Build for the default target CPU"
+        )]
+        no_native: bool,
     },
     #[command(about = "Update the utils crate to the latest version")]
     Update {
         #[arg(help = "Component to update (currently only 'utils')")]
         component: String,
+        #[arg(
+            long,
+            help = "Also rewrite day crates written for an older utils, keeping main.rs.bak"
+        )]
+        migrate: bool,
     },
 }
 
@@ -51,19 +206,77 @@ fn main() -> Result<()> {
     match cli.command {
         Commands::Init { name } => commands::init_project(&name),
         Commands::Add { day } => commands::add_day(day),
-        Commands::Analytics { file_path } => commands::run_analytics(&file_path),
-        Commands::Time { day, part1, part2 } => {
+        Commands::Remove { day, force } => commands::remove_day(day, force),
+        Commands::Analytics {
+            file_path,
+            format,
+            timeout,
+            budget,
+        } => commands::run_analytics(
+            file_path.as_deref(),
+            format,
+            timeout.map(Duration::from_secs),
+            &budget,
+        ),
+        Commands::History { day, last } => commands::show_history(day, last),
+        Commands::Compare {
+            baseline,
+            threshold,
+        } => commands::compare_runs(baseline.as_deref(), threshold),
+        Commands::Run {
+            day,
+            all_inputs,
+            all,
+        } => {
+            if all {
+                commands::run_all()
+            } else {
+                commands::run_day(day, all_inputs)
+            }
+        }
+        Commands::Time {
+            day,
+            part1,
+            part2,
+            timeout,
+            spans,
+        } => {
             let part = match (part1, part2) {
                 (true, true) => anyhow::bail!("Cannot specify both --part1 and --part2"),
                 (true, false) => commands::TimePart::Part1,
                 (false, true) => commands::TimePart::Part2,
                 (false, false) => commands::TimePart::Both,
             };
-            commands::time_day(day, part)
+            commands::time_day(day, part, timeout.map(Duration::from_secs), spans)
+        }
+        Commands::Bench {
+            baseline,
+            days,
+            iterations,
+            timeout,
+        } => commands::bench_against(&baseline, &days, iterations, Duration::from_secs(timeout)),
+        Commands::Watch { day, test, timeout } => {
+            commands::watch_day(day, test, Duration::from_secs(timeout))
+        }
+        Commands::Visualize { day, export, fps } => {
+            commands::visualize_day(day, export.as_deref(), fps)
+        }
+        Commands::Verify => commands::verify_answers(),
+        Commands::Profile {
+            profile,
+            native,
+            no_native,
+        } => {
+            let native = match (native, no_native) {
+                (true, _) => Some(true),
+                (_, true) => Some(false),
+                _ => None,
+            };
+            commands::configure_profile(profile, native)
         }
-        Commands::Update { component } => {
+        Commands::Update { component, migrate } => {
             if component.to_lowercase() == "utils" {
-                commands::update_utils()
+                commands::update_utils(migrate)
             } else {
                 anyhow::bail!(
                     "Unknown component '{component}'. Currently only 'utils' is supported."
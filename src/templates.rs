@@ -4,10 +4,27 @@ pub const WORKSPACE_README: &str = include_str!("../templates/workspace_readme.m
 pub const UTILS_CARGO_TOML: &str = include_str!("../templates/utils/Cargo.toml");
 pub const DAY_CARGO_TOML: &str = include_str!("../templates/day/Cargo.toml");
 pub const DAY_MAIN_RS: &str = include_str!("../templates/day/main.rs");
+pub const DAY_LIB_RS: &str = include_str!("../templates/day/lib.rs");
+pub const DAY_LIB_SINGLE_PART_RS: &str = include_str!("../templates/day/lib_single_part.rs");
 pub const DAY_README: &str = include_str!("../templates/day/README.md");
+pub const DAY_ANSWERS: &str = include_str!("../templates/day/answers.txt");
+pub const RUNNER_CARGO_TOML: &str = include_str!("../templates/runner/Cargo.toml");
+pub const RUNNER_MAIN_RS: &str = include_str!("../templates/runner/main.rs");
 
 // Utils crate source files
 pub const UTILS_LIB_RS: &str = include_str!("../templates/utils/lib.rs");
+pub const UTILS_DEBUG_RS: &str = include_str!("../templates/utils/debug.rs");
+pub const UTILS_PANICS_RS: &str = include_str!("../templates/utils/panics.rs");
+pub const UTILS_GRID_RS: &str = include_str!("../templates/utils/grid.rs");
+pub const UTILS_SPARSE_GRID_RS: &str = include_str!("../templates/utils/sparse_grid.rs");
+pub const UTILS_HEX_RS: &str = include_str!("../templates/utils/hex.rs");
 pub const UTILS_DIRECTIONS_RS: &str = include_str!("../templates/utils/directions.rs");
 pub const UTILS_POINTS_RS: &str = include_str!("../templates/utils/points.rs");
 pub const UTILS_POINT3D_RS: &str = include_str!("../templates/utils/point3d.rs");
+pub const UTILS_SPANS_RS: &str = include_str!("../templates/utils/spans.rs");
+pub const UTILS_ERROR_RS: &str = include_str!("../templates/utils/error.rs");
+pub const UTILS_TIMER_RS: &str = include_str!("../templates/utils/timer.rs");
+pub const UTILS_PARAMS_RS: &str = include_str!("../templates/utils/params.rs");
+pub const UTILS_TESTING_RS: &str = include_str!("../templates/utils/testing.rs");
+pub const UTILS_REGISTRY_RS: &str = include_str!("../templates/utils/registry.rs");
+pub const UTILS_VISUALIZE_RS: &str = include_str!("../templates/utils/visualize.rs");
@@ -0,0 +1,35 @@
+use utils::{Solution, SolutionError, try_parse_lines};
+
+pub struct Day{DAY};
+
+impl Solution for Day{DAY} {
+    type Input = Vec<i32>;
+    type Output1 = i32;
+    type Output2 = i32;
+    type Params = ();
+
+    fn try_parse_input(&self, input: &str) -> Result<Self::Input, SolutionError> {
+        try_parse_lines(input)
+    }
+
+    fn try_part1(&self, _data: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        // TODO: Implement part 1
+        Ok(0)
+    }
+
+    fn try_part2(&self, _data: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        // TODO: Implement part 2
+        Ok(0)
+    }
+}
+
+utils::register_day!(Day{DAY});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    utils::aoc_tests!(Day{DAY},
+        example(inline "") => part1 = 0, part2 = 0;
+    );
+}
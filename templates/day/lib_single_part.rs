@@ -0,0 +1,35 @@
+use utils::{NoPart, Solution, SolutionError, try_parse_lines};
+
+pub struct Day{DAY};
+
+impl Solution for Day{DAY} {
+    type Input = Vec<i32>;
+    type Output1 = i32;
+    // The last day has no second puzzle
+    type Output2 = NoPart;
+    type Params = ();
+
+    fn try_parse_input(&self, input: &str) -> Result<Self::Input, SolutionError> {
+        try_parse_lines(input)
+    }
+
+    fn try_part1(&self, _data: &Self::Input) -> Result<Self::Output1, SolutionError> {
+        // TODO: Implement part 1
+        Ok(0)
+    }
+
+    fn try_part2(&self, _data: &Self::Input) -> Result<Self::Output2, SolutionError> {
+        Ok(NoPart)
+    }
+}
+
+utils::register_day!(Day{DAY});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    utils::aoc_tests!(Day{DAY},
+        example(inline "") => part1 = 0;
+    );
+}
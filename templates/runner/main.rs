@@ -0,0 +1,5 @@
+// Generated by aoc-cli: `add` and `remove` keep the day list in sync.
+
+fn main() {
+    utils::run_days(&[{DAYS}]);
+}
@@ -0,0 +1,101 @@
+//! Debug output that stays out of timing runs.
+//!
+//! ```ignore
+//! utils::debug!("queue has {} states", queue.len());
+//! utils::debug!(2; "visiting {pos:?}");
+//! ```
+//!
+//! Messages go to stderr, each with the file and line it came from, when the
+//! verbosity is at least their level (1 unless given before a `;`). The verbosity is
+//! the number of `v`s in `-v`/`-vv` (`cargo run -- -vv`), or else the `AOC_DEBUG`
+//! environment variable, which also works under `cargo test`. In `--timing`,
+//! `--part1` and `--part2` runs it is always 0, and the arguments of `debug!` are not
+//! even evaluated.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Not set yet: read from `AOC_DEBUG` on first use.
+const UNSET: u8 = u8::MAX;
+const MAX_LEVEL: u8 = UNSET - 1;
+
+static LEVEL: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Prints a debug message to stderr if the verbosity is high enough; see the
+/// [module docs](self).
+#[macro_export]
+macro_rules! debug {
+    ($level:expr; $($arg:tt)+) => {
+        if $crate::debug_level() >= $level {
+            eprintln!("[{}:{}] {}", file!(), line!(), format_args!($($arg)+));
+        }
+    };
+    ($($arg:tt)+) => {
+        $crate::debug!(1; $($arg)+)
+    };
+}
+
+pub fn set_debug_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn debug_level() -> u8 {
+    match LEVEL.load(Ordering::Relaxed) {
+        UNSET => {
+            let level = std::env::var("AOC_DEBUG").map_or(0, |value| env_level(&value));
+            set_debug_level(level);
+            level
+        }
+        level => level,
+    }
+}
+
+/// The verbosity given by `-v`, `-vv`, ... arguments, if any. Used by `run_solution!`.
+#[must_use]
+pub fn debug_level_from_args(args: &[String]) -> Option<u8> {
+    let count: usize = args
+        .iter()
+        .filter_map(|arg| arg.strip_prefix('-'))
+        .filter(|flags| !flags.is_empty() && flags.chars().all(|c| c == 'v'))
+        .map(str::len)
+        .sum();
+
+    (count > 0).then(|| u8::try_from(count).map_or(MAX_LEVEL, |count| count.min(MAX_LEVEL)))
+}
+
+/// `AOC_DEBUG=2` is level 2; any other non-empty value but `0` is level 1.
+fn env_level(value: &str) -> u8 {
+    match value.trim() {
+        "" | "0" => 0,
+        value => value.parse().unwrap_or(1).min(MAX_LEVEL),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_debug_level_from_args() {
+        assert_eq!(debug_level_from_args(&args(&["--timing"])), None);
+        assert_eq!(debug_level_from_args(&args(&["-v"])), Some(1));
+        assert_eq!(
+            debug_level_from_args(&args(&["-vv", "--input", "a.txt"])),
+            Some(2)
+        );
+        assert_eq!(debug_level_from_args(&args(&["-v", "-v", "-v"])), Some(3));
+        assert_eq!(debug_level_from_args(&args(&["-", "--visualize"])), None);
+    }
+
+    #[test]
+    fn test_env_level() {
+        assert_eq!(env_level(""), 0);
+        assert_eq!(env_level("0"), 0);
+        assert_eq!(env_level("2"), 2);
+        assert_eq!(env_level("yes"), 1);
+    }
+}
@@ -1,5 +1,7 @@
 use std::ops::Add;
 
+use crate::Point;
+
 #[derive(Debug, PartialEq, Clone, Copy, Hash, Eq)]
 pub enum Direction {
     Up,
@@ -57,6 +59,16 @@ impl Add<Direction> for (usize, usize) {
     }
 }
 
+// Point + direction, for unbounded grids
+impl Add<Direction> for Point<i64> {
+    type Output = Point<i64>;
+
+    fn add(self, d: Direction) -> Self::Output {
+        let (dx, dy) = d.into();
+        Point::new(self.x + dx as i64, self.y + dy as i64)
+    }
+}
+
 impl From<char> for Direction {
     fn from(value: char) -> Self {
         match value {
@@ -0,0 +1,153 @@
+//! Errors for fallible parsing and solving, reported with the offending input line.
+
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/// A 1-based position in the puzzle input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Error returned by [`crate::Solution::try_parse_input`], [`crate::Solution::try_part1`]
+/// and [`crate::Solution::try_part2`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionError {
+    message: String,
+    location: Option<Location>,
+    stage: Option<&'static str>,
+    panic: bool,
+}
+
+impl SolutionError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            location: None,
+            stage: None,
+            panic: false,
+        }
+    }
+
+    /// An error at a 1-based line and column of the input.
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            location: Some(Location { line, column }),
+            ..Self::new(message)
+        }
+    }
+
+    /// A panic caught while solving, described by `message`. See [`crate::catch_panic`].
+    pub fn from_panic(message: impl Into<String>) -> Self {
+        Self {
+            panic: true,
+            ..Self::new(message)
+        }
+    }
+
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    #[must_use]
+    pub const fn location(&self) -> Option<Location> {
+        self.location
+    }
+
+    #[must_use]
+    pub const fn stage(&self) -> Option<&'static str> {
+        self.stage
+    }
+
+    #[must_use]
+    pub const fn is_panic(&self) -> bool {
+        self.panic
+    }
+
+    /// Records which step failed ("parse", "part 1" or "part 2"), unless already set.
+    #[must_use]
+    pub fn in_stage(mut self, stage: &'static str) -> Self {
+        self.stage.get_or_insert(stage);
+        self
+    }
+
+    /// The error followed by the offending input line with a caret under the column.
+    #[must_use]
+    pub fn report(&self, input: &str) -> String {
+        let mut report = format!("error: {self}");
+
+        if let Some(Location { line, column }) = self.location
+            && let Some(text) = input.lines().nth(line.saturating_sub(1))
+        {
+            let gutter = " ".repeat(line.to_string().len());
+            let padding = " ".repeat(column.saturating_sub(1));
+            let _ = write!(
+                report,
+                "\n{gutter} |\n{line} | {text}\n{gutter} | {padding}^"
+            );
+        }
+
+        report
+    }
+}
+
+impl fmt::Display for SolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(stage) = self.stage {
+            write!(f, "{stage}: ")?;
+        }
+
+        if let Some(Location { line, column }) = self.location {
+            write!(f, "line {line}, column {column}: ")?;
+        }
+
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SolutionError {}
+
+/// Parses every non-empty line with [`FromStr`], failing on the first line that
+/// does not parse instead of silently skipping it.
+pub fn try_parse_lines<T>(input: &str) -> Result<Vec<T>, SolutionError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let column = line.len() - line.trim_start().len() + 1;
+            line.trim()
+                .parse()
+                .map_err(|err| SolutionError::at(index + 1, column, format!("{err}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_parse_lines() {
+        assert_eq!(try_parse_lines::<i32>("1\n\n 2\n"), Ok(vec![1, 2]));
+
+        let err = try_parse_lines::<i32>("1\n  x2\n").unwrap_err();
+        assert_eq!(err.location(), Some(Location { line: 2, column: 3 }));
+    }
+
+    #[test]
+    fn test_report_points_at_column() {
+        let err = SolutionError::at(2, 3, "bad digit").in_stage("parse");
+
+        assert_eq!(
+            err.report("1\n  x2\n"),
+            "error: parse: line 2, column 3: bad digit\n  |\n2 |   x2\n  |   ^"
+        );
+    }
+}
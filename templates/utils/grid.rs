@@ -0,0 +1,738 @@
+//! A rectangular grid of cells, for the puzzles whose input is a character map.
+//!
+//! ```ignore
+//! let grid = Grid::parse(input, |c| c == '#')?;
+//! let start = grid.find(&'S').ok_or_else(|| SolutionError::new("no start"))?;
+//! for next in grid.neighbors(start) {
+//!     if !grid[next] { /* ... */ }
+//! }
+//! ```
+//!
+//! Positions are `Point<usize>` or `(usize, usize)`, with `x` the column and `y` the
+//! row, counted from the top left. Stepping off the grid with `(x, y) + direction`
+//! wraps around to a huge coordinate, so `get` simply returns `None` for it.
+//!
+//! Rotations, flips, padding and tiling return a new grid; [`Grid::view`] borrows a
+//! window of the grid instead.
+
+use std::convert::Infallible;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::{Direction, Point, SolutionError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// A `width` x `height` grid with every cell set to `f(pos)`.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point<usize>) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses one row per line, mapping every character to a cell. Empty lines are
+    /// skipped; rows of different lengths are an error.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, SolutionError> {
+        Self::try_parse(input, |c| Ok::<_, Infallible>(cell(c)))
+    }
+
+    /// Like [`Grid::parse`], for cells that can fail to parse, e.g. with
+    /// `|c| c.to_digit(10).ok_or("not a digit")`. The error points at the character.
+    pub fn try_parse<E: fmt::Display>(
+        input: &str,
+        mut cell: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, SolutionError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (index, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let before = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                let value = cell(c)
+                    .map_err(|err| SolutionError::at(index + 1, column + 1, err.to_string()))?;
+                cells.push(value);
+            }
+
+            let row_width = cells.len() - before;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(SolutionError::at(
+                        index + 1,
+                        1,
+                        format!("row is {row_width} wide, expected {width}"),
+                    ));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        let width = width.ok_or_else(|| SolutionError::new("empty grid"))?;
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: impl Into<Point<usize>>) -> bool {
+        let pos = pos.into();
+        pos.x < self.width && pos.y < self.height
+    }
+
+    /// The cell at `pos`, or `None` if it is outside the grid.
+    pub fn get(&self, pos: impl Into<Point<usize>>) -> Option<&T> {
+        let index = self.index_of(pos.into())?;
+        Some(&self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: impl Into<Point<usize>>) -> Option<&mut T> {
+        let index = self.index_of(pos.into())?;
+        Some(&mut self.cells[index])
+    }
+
+    /// The first position, in reading order, holding `value`.
+    pub fn find(&self, value: &T) -> Option<Point<usize>>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Every position holding `value`, in reading order.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point<usize>> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(pos, _)| pos)
+    }
+
+    /// Every position with its cell, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.point_of(index), cell))
+    }
+
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The rows from top to bottom. Also what [`crate::frame_grid`] takes:
+    /// `frame_grid("title", grid.rows(), highlights)`.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Not `chunks`, which panics for a grid without columns
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    #[must_use]
+    pub fn row_mut(&mut self, y: usize) -> Option<&mut [T]> {
+        (y < self.height).then(|| &mut self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The rows from top to bottom, e.g. to tilt every row of a platform in place.
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        let width = self.width;
+        let mut rest = &mut self.cells[..];
+        (0..self.height).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(width);
+            rest = tail;
+            row
+        })
+    }
+
+    /// The cells of column `x` from top to bottom, or `None` if it is outside the grid.
+    #[must_use]
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// The columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// The up to 4 positions next to `pos` (see [`Direction::cardinal`]) that are on
+    /// the grid.
+    pub fn neighbors(&self, pos: impl Into<Point<usize>>) -> impl Iterator<Item = Point<usize>> {
+        self.neighbors_in(pos.into(), Direction::cardinal())
+    }
+
+    /// The up to 8 positions around `pos`, diagonals included (see [`Direction::all`]),
+    /// that are on the grid.
+    pub fn all_neighbors(
+        &self,
+        pos: impl Into<Point<usize>>,
+    ) -> impl Iterator<Item = Point<usize>> {
+        self.neighbors_in(pos.into(), Direction::all())
+    }
+
+    fn neighbors_in<const N: usize>(
+        &self,
+        pos: Point<usize>,
+        directions: [Direction; N],
+    ) -> impl Iterator<Item = Point<usize>> {
+        directions
+            .into_iter()
+            .map(move |direction| Point::from((pos.x, pos.y) + direction))
+            .filter(|&next| self.contains(next))
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// A borrowed `width` x `height` window whose top left cell is `top_left`, or
+    /// `None` if it does not fit in the grid.
+    pub fn view(
+        &self,
+        top_left: impl Into<Point<usize>>,
+        width: usize,
+        height: usize,
+    ) -> Option<GridView<'_, T>> {
+        GridView::new(self, top_left.into(), width, height)
+    }
+
+    fn index_of(&self, pos: Point<usize>) -> Option<usize> {
+        self.contains(pos).then(|| pos.y * self.width + pos.x)
+    }
+
+    fn point_of(&self, index: usize) -> Point<usize> {
+        Point::new(index % self.width, index / self.width)
+    }
+
+    fn index_or_panic(&self, pos: Point<usize>) -> usize {
+        self.index_of(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos} is outside the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+/// Transformations, each returning a new grid.
+impl<T: Clone> Grid<T> {
+    /// Swaps rows and columns: the cell at `(x, y)` moves to `(y, x)`.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| self[(pos.y, pos.x)].clone())
+    }
+
+    /// Rotates by 90°, so the left column becomes the top row.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[(pos.y, self.height - 1 - pos.x)].clone()
+        })
+    }
+
+    /// Rotates by 90°, so the top row becomes the left column.
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::from_fn(self.height, self.width, |pos| {
+            self[(self.width - 1 - pos.y, pos.x)].clone()
+        })
+    }
+
+    /// Mirrors left to right.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.width, self.height, |pos| {
+            self[(self.width - 1 - pos.x, pos.y)].clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.width, self.height, |pos| {
+            self[(pos.x, self.height - 1 - pos.y)].clone()
+        })
+    }
+
+    /// The 8 ways to rotate and flip the grid, starting with the grid itself: every
+    /// rotation by 90°, each followed by its horizontal flip.
+    #[must_use]
+    pub fn symmetries(&self) -> Vec<Self> {
+        let mut symmetries = Vec::with_capacity(8);
+        let mut rotated = self.clone();
+
+        for _ in 0..4 {
+            let flipped = rotated.flip_horizontal();
+            let next = rotated.rotate_clockwise();
+            symmetries.push(rotated);
+            symmetries.push(flipped);
+            rotated = next;
+        }
+
+        symmetries
+    }
+
+    /// Surrounds the grid with `amount` cells of `value` on every side, so the cell
+    /// at `(x, y)` moves to `(x + amount, y + amount)`.
+    #[must_use]
+    pub fn pad(&self, amount: usize, value: T) -> Self {
+        Self::from_fn(
+            self.width + 2 * amount,
+            self.height + 2 * amount,
+            |pos| match self.get((pos.x.wrapping_sub(amount), pos.y.wrapping_sub(amount))) {
+                Some(cell) => cell.clone(),
+                None => value.clone(),
+            },
+        )
+    }
+
+    /// Repeats the grid `across` times to the right and `down` times downwards.
+    #[must_use]
+    pub fn tile(&self, across: usize, down: usize) -> Self {
+        Self::from_fn(self.width * across, self.height * down, |pos| {
+            self[(pos.x % self.width, pos.y % self.height)].clone()
+        })
+    }
+
+    /// Joins a grid of equally sized tiles into one grid, e.g. once the tiles of a
+    /// picture are assembled and their borders trimmed with [`GridView::to_grid`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no tiles, or they are not all the same size.
+    #[must_use]
+    pub fn from_tiles(tiles: &Grid<Self>) -> Self {
+        let first = &tiles.cells[0];
+        let (tile_width, tile_height) = (first.width, first.height);
+        assert!(
+            tiles
+                .cells
+                .iter()
+                .all(|tile| tile.width == tile_width && tile.height == tile_height),
+            "tiles must all be {tile_width}x{tile_height}"
+        );
+
+        Self::from_fn(
+            tiles.width * tile_width,
+            tiles.height * tile_height,
+            |pos| {
+                let tile = &tiles[(pos.x / tile_width, pos.y / tile_height)];
+                tile[(pos.x % tile_width, pos.y % tile_height)].clone()
+            },
+        )
+    }
+}
+
+/// Parses a grid of characters, e.g. `let grid: Grid<char> = input.parse()?;`.
+impl FromStr for Grid<char> {
+    type Err = SolutionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, |c| c)
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &T {
+        &self.cells[self.index_or_panic(pos)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point<usize>) -> &mut T {
+        let index = self.index_or_panic(pos);
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        &self[Point::from(pos)]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        &mut self[Point::from(pos)]
+    }
+}
+
+/// One line per row, each cell written with its own `Display`.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+fn write_rows<'a, T: fmt::Display + 'a>(
+    f: &mut fmt::Formatter<'_>,
+    rows: impl Iterator<Item = &'a [T]>,
+) -> fmt::Result {
+    for (y, row) in rows.enumerate() {
+        if y > 0 {
+            writeln!(f)?;
+        }
+        for cell in row {
+            write!(f, "{cell}")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A rectangular window into a [`Grid`], from [`Grid::view`]. Positions are relative
+/// to the window's top left cell.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    origin: Point<usize>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    fn new(grid: &'a Grid<T>, origin: Point<usize>, width: usize, height: usize) -> Option<Self> {
+        fits(origin, width, height, (grid.width, grid.height)).then_some(Self {
+            grid,
+            origin,
+            width,
+            height,
+        })
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Where the window's top left cell is in the grid.
+    #[must_use]
+    pub const fn origin(&self) -> Point<usize> {
+        self.origin
+    }
+
+    pub fn contains(&self, pos: impl Into<Point<usize>>) -> bool {
+        let pos = pos.into();
+        pos.x < self.width && pos.y < self.height
+    }
+
+    /// The cell at `pos` in the window, or `None` if it is outside the window.
+    pub fn get(&self, pos: impl Into<Point<usize>>) -> Option<&'a T> {
+        let pos = pos.into();
+        if !self.contains(pos) {
+            return None;
+        }
+
+        self.grid.get(self.origin + pos)
+    }
+
+    #[must_use]
+    pub fn row(&self, y: usize) -> Option<&'a [T]> {
+        if y >= self.height {
+            return None;
+        }
+
+        let row = self.grid.row(self.origin.y + y)?;
+        Some(&row[self.origin.x..self.origin.x + self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + use<'a, T> {
+        let view = *self;
+        (0..self.height).filter_map(move |y| view.row(y))
+    }
+
+    #[must_use]
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &'a T> + use<'a, T>> {
+        (x < self.width).then(|| self.rows().map(move |row| &row[x]))
+    }
+
+    /// Every position in the window with its cell, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<usize>, &'a T)> + use<'a, T> {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, cell)| (Point::new(x, y), cell))
+        })
+    }
+
+    /// A smaller window inside this one.
+    pub fn view(
+        &self,
+        top_left: impl Into<Point<usize>>,
+        width: usize,
+        height: usize,
+    ) -> Option<Self> {
+        let top_left = top_left.into();
+        fits(top_left, width, height, (self.width, self.height)).then_some(Self {
+            origin: self.origin + top_left,
+            width,
+            height,
+            ..*self
+        })
+    }
+
+    /// Copies the window into a grid of its own.
+    #[must_use]
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |pos| self[pos].clone())
+    }
+}
+
+/// Whether a `width` x `height` window at `top_left` fits in an area of `size`.
+fn fits(top_left: Point<usize>, width: usize, height: usize, size: (usize, usize)) -> bool {
+    top_left
+        .x
+        .checked_add(width)
+        .is_some_and(|right| right <= size.0)
+        && top_left
+            .y
+            .checked_add(height)
+            .is_some_and(|bottom| bottom <= size.1)
+}
+
+impl<T> Index<Point<usize>> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<usize>) -> &T {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "position {pos} is outside the {}x{} view",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        &self[Point::from(pos)]
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rows(f, self.rows())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Location;
+
+    const MAP: &str = "#S.\n.#.\n..E\n";
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[(1, 0)], 'S');
+        assert_eq!(grid[Point::new(2, 2)], 'E');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, usize::MAX)), None);
+        assert_eq!(grid.to_string(), MAP.trim_end());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Grid::parse("##\n#\n", |c| c).unwrap_err();
+        assert_eq!(err.location(), Some(Location { line: 2, column: 1 }));
+
+        let err = Grid::try_parse("12\n3x\n", |c| c.to_digit(10).ok_or("not a digit")).unwrap_err();
+        assert_eq!(err.location(), Some(Location { line: 2, column: 2 }));
+
+        assert!(Grid::parse("\n", |c| c).is_err());
+    }
+
+    #[test]
+    fn test_find() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+
+        assert_eq!(grid.find(&'S'), Some(Point::new(1, 0)));
+        assert_eq!(grid.find(&'x'), None);
+        assert_eq!(
+            grid.find_all(&'#').collect::<Vec<_>>(),
+            [Point::new(0, 0), Point::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::try_parse("12\n34\n", |c| c.to_digit(10).ok_or("not a digit")).unwrap();
+
+        assert_eq!(grid.rows().collect::<Vec<_>>(), [[1, 2], [3, 4]]);
+        assert_eq!(grid.row(1), Some(&[3, 4][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).unwrap().collect::<Vec<_>>(), [&2, &4]);
+
+        let sums: Vec<u32> = grid.columns().map(Iterator::sum).collect();
+        assert_eq!(sums, [4, 6]);
+    }
+
+    #[test]
+    fn test_empty_grids() {
+        let mut no_columns = Grid::new(0, 2, '.');
+        assert_eq!(no_columns.rows().collect::<Vec<_>>(), [[], []]);
+        assert_eq!(no_columns.rows_mut().count(), 2);
+        assert_eq!(no_columns.columns().count(), 0);
+        assert!(no_columns.column(0).is_none());
+        assert_eq!(no_columns.to_string(), "\n");
+        assert_eq!(no_columns.view((0, 1), 0, 1).unwrap().rows().count(), 1);
+
+        let no_rows = Grid::new(2, 0, '.');
+        assert_eq!(no_rows.rows().count(), 0);
+        assert_eq!(
+            no_rows.columns().map(Iterator::count).collect::<Vec<_>>(),
+            [0, 0]
+        );
+        assert_eq!(no_rows.to_string(), "");
+        assert_eq!(no_rows.transpose(), no_columns);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+
+        assert_eq!(grid.neighbors((0, 0)).count(), 2);
+        assert_eq!(grid.neighbors((1, 1)).count(), 4);
+        assert_eq!(grid.all_neighbors((0, 0)).count(), 3);
+        assert_eq!(grid.all_neighbors((1, 1)).count(), 8);
+        assert!(grid.neighbors((2, 1)).all(|pos| grid.contains(pos)));
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let grid: Grid<char> = "ab\ncd\nef\n".parse().unwrap();
+
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "bdf\nace");
+        assert_eq!(grid.flip_horizontal().to_string(), "ba\ndc\nfe");
+        assert_eq!(grid.flip_vertical().to_string(), "ef\ncd\nab");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_symmetries() {
+        let grid: Grid<char> = "ab\ncd\n".parse().unwrap();
+        let symmetries = grid.symmetries();
+
+        assert_eq!(symmetries[0], grid);
+        assert!(symmetries.contains(&grid.transpose()));
+        assert!(symmetries.contains(&grid.flip_vertical()));
+
+        let distinct: std::collections::HashSet<_> = symmetries.iter().collect();
+        assert_eq!(distinct.len(), 8);
+    }
+
+    #[test]
+    fn test_view() {
+        let grid: Grid<char> = "abcd\nefgh\nijkl\n".parse().unwrap();
+        let view = grid.view((1, 1), 3, 2).unwrap();
+
+        assert_eq!(view.to_string(), "fgh\njkl");
+        assert_eq!(view[(0, 0)], 'f');
+        assert_eq!(view.get((3, 0)), None);
+        assert_eq!(view.row(1), Some(&['j', 'k', 'l'][..]));
+        assert_eq!(view.column(2).unwrap().collect::<String>(), "hl");
+        assert_eq!(view.view((1, 0), 2, 2).unwrap().to_string(), "gh\nkl");
+        assert_eq!(view.to_grid().find(&'k'), Some(Point::new(1, 1)));
+
+        assert!(grid.view((1, 1), 4, 1).is_none());
+        assert!(view.view((0, 1), 1, 2).is_none());
+    }
+
+    #[test]
+    fn test_pad_and_tile() {
+        let grid: Grid<char> = "ab\n".parse().unwrap();
+
+        assert_eq!(grid.pad(1, '.').to_string(), "....\n.ab.\n....");
+        assert_eq!(grid.tile(2, 2).to_string(), "abab\nabab");
+
+        let tiles = Grid::from_fn(2, 1, |pos| grid.map(|&c| if pos.x == 0 { c } else { '#' }));
+        assert_eq!(Grid::from_tiles(&tiles).to_string(), "ab##");
+    }
+
+    #[test]
+    fn test_map_and_index_mut() {
+        let mut grid = Grid::parse(MAP, |c| c == '#')
+            .unwrap()
+            .map(|&wall| u8::from(wall));
+        grid[(2, 0)] = 7;
+        grid.rows_mut().for_each(<[u8]>::reverse);
+
+        assert_eq!(grid.to_string(), "701\n010\n000");
+    }
+}
@@ -0,0 +1,451 @@
+//! Hexagonal grids, in axial coordinates ([`Hex`]) with cube coordinates ([`Cube`])
+//! for the maths that is easier in three axes.
+//!
+//! ```ignore
+//! let steps: Vec<FlatDirection> = parse_hex_path("ne,ne,s,s")?;
+//! let end = steps.into_iter().fold(Hex::ORIGIN, |hex, step| hex + step);
+//! assert_eq!(end.distance(Hex::ORIGIN), 2);
+//! ```
+//!
+//! Hex grids come in two orientations, with their own direction names:
+//! [`FlatDirection`] for flat-topped hexes (`n`, `ne`, `se`, `s`, `sw`, `nw`) and
+//! [`PointyDirection`] for pointy-topped ones (`e`, `w`, `ne`, `nw`, `se`, `sw`).
+//! Both step through the same coordinates, so distances, neighbors and rings work
+//! the same for either. `r` grows downwards, like `y` in [`crate::Point`].
+
+use std::fmt;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
+
+use crate::{Point, SparseGrid};
+
+/// A hex in axial coordinates: `q` is the column, `r` the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// A hex in cube coordinates, where `q + r + s == 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Cube {
+    pub q: i64,
+    pub r: i64,
+    pub s: i64,
+}
+
+/// How hexes are drawn: with a flat edge or a corner at the top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexLayout {
+    Flat,
+    Pointy,
+}
+
+/// The six neighbor offsets, going around counterclockwise from the right.
+const OFFSETS: [Hex; 6] = [
+    Hex::new(1, 0),
+    Hex::new(1, -1),
+    Hex::new(0, -1),
+    Hex::new(-1, 0),
+    Hex::new(-1, 1),
+    Hex::new(0, 1),
+];
+
+impl Hex {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// The third cube coordinate.
+    #[must_use]
+    pub const fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// The number of steps between two hexes.
+    #[must_use]
+    pub const fn distance(&self, other: Self) -> i64 {
+        let dq = (self.q - other.q).abs();
+        let dr = (self.r - other.r).abs();
+        let ds = (self.s() - other.s()).abs();
+        (dq + dr + ds) / 2
+    }
+
+    /// The 6 hexes next to this one.
+    #[must_use]
+    pub fn neighbors(&self) -> [Self; 6] {
+        OFFSETS.map(|offset| *self + offset)
+    }
+
+    /// The `6 * radius` hexes exactly `radius` steps away, going around the ring.
+    /// A ring of radius 0 is the hex itself.
+    #[must_use]
+    pub fn ring(&self, radius: u32) -> Vec<Self> {
+        if radius == 0 {
+            return vec![*self];
+        }
+
+        let radius = i64::from(radius);
+        let mut hex = *self + Self::new(-radius, radius);
+        let mut ring = Vec::new();
+
+        for offset in OFFSETS {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex + offset;
+            }
+        }
+
+        ring
+    }
+
+    /// Every hex at most `radius` steps away, this one included.
+    pub fn within(&self, radius: u32) -> impl Iterator<Item = Self> {
+        let center = *self;
+        let radius = i64::from(radius);
+
+        (-radius..=radius).flat_map(move |q| {
+            let r_range = (-radius).max(-q - radius)..=radius.min(-q + radius);
+            r_range.map(move |r| center + Self::new(q, r))
+        })
+    }
+
+    /// The center of the hex in a plane where hexes have a radius of 1, with `y`
+    /// growing downwards.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn to_pixel(&self, layout: HexLayout) -> (f64, f64) {
+        let sqrt3 = 3f64.sqrt();
+        let (q, r) = (self.q as f64, self.r as f64);
+
+        match layout {
+            HexLayout::Flat => (1.5 * q, sqrt3 / 2.0 * q + sqrt3 * r),
+            HexLayout::Pointy => (sqrt3 * q + sqrt3 / 2.0 * r, 1.5 * r),
+        }
+    }
+
+    /// Integer text coordinates: pointy hexes are two columns apart with every other
+    /// row shifted by one; flat hexes are two rows apart with every other column
+    /// shifted by one. See [`render_hexes`].
+    #[must_use]
+    pub const fn doubled(&self, layout: HexLayout) -> Point<i64> {
+        match layout {
+            HexLayout::Flat => Point {
+                x: self.q,
+                y: 2 * self.r + self.q,
+            },
+            HexLayout::Pointy => Point {
+                x: 2 * self.q + self.r,
+                y: self.r,
+            },
+        }
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.q, self.r)
+    }
+}
+
+impl Cube {
+    /// # Panics
+    ///
+    /// Panics in debug builds if `q + r + s != 0`.
+    #[must_use]
+    pub const fn new(q: i64, r: i64, s: i64) -> Self {
+        debug_assert!(q + r + s == 0, "cube coordinates must sum to 0");
+        Self { q, r, s }
+    }
+
+    #[must_use]
+    pub const fn distance(&self, other: Self) -> i64 {
+        ((self.q - other.q).abs() + (self.r - other.r).abs() + (self.s - other.s).abs()) / 2
+    }
+
+    /// Rotates by 60° clockwise around the origin.
+    #[must_use]
+    pub const fn rotate_clockwise(&self) -> Self {
+        Self::new(-self.r, -self.s, -self.q)
+    }
+
+    /// Rotates by 60° counterclockwise around the origin.
+    #[must_use]
+    pub const fn rotate_counterclockwise(&self) -> Self {
+        Self::new(-self.s, -self.q, -self.r)
+    }
+}
+
+impl From<Hex> for Cube {
+    fn from(hex: Hex) -> Self {
+        Self::new(hex.q, hex.r, hex.s())
+    }
+}
+
+impl From<Cube> for Hex {
+    fn from(cube: Cube) -> Self {
+        Self::new(cube.q, cube.r)
+    }
+}
+
+/// The directions between flat-topped hexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlatDirection {
+    N,
+    NE,
+    SE,
+    S,
+    SW,
+    NW,
+}
+
+impl FlatDirection {
+    #[must_use]
+    pub fn all() -> [Self; 6] {
+        [Self::N, Self::NE, Self::SE, Self::S, Self::SW, Self::NW]
+    }
+
+    /// The step to the neighbor in this direction.
+    #[must_use]
+    pub const fn offset(&self) -> Hex {
+        match self {
+            Self::N => Hex::new(0, -1),
+            Self::NE => Hex::new(1, -1),
+            Self::SE => Hex::new(1, 0),
+            Self::S => Hex::new(0, 1),
+            Self::SW => Hex::new(-1, 1),
+            Self::NW => Hex::new(-1, 0),
+        }
+    }
+}
+
+impl FromStr for FlatDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "n" => Ok(Self::N),
+            "ne" => Ok(Self::NE),
+            "se" => Ok(Self::SE),
+            "s" => Ok(Self::S),
+            "sw" => Ok(Self::SW),
+            "nw" => Ok(Self::NW),
+            _ => Err(format!("invalid hex direction '{s}'")),
+        }
+    }
+}
+
+impl Add<FlatDirection> for Hex {
+    type Output = Self;
+
+    fn add(self, direction: FlatDirection) -> Self {
+        self + direction.offset()
+    }
+}
+
+/// The directions between pointy-topped hexes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointyDirection {
+    E,
+    W,
+    NE,
+    NW,
+    SE,
+    SW,
+}
+
+impl PointyDirection {
+    #[must_use]
+    pub fn all() -> [Self; 6] {
+        [Self::E, Self::W, Self::NE, Self::NW, Self::SE, Self::SW]
+    }
+
+    /// The step to the neighbor in this direction.
+    #[must_use]
+    pub const fn offset(&self) -> Hex {
+        match self {
+            Self::E => Hex::new(1, 0),
+            Self::W => Hex::new(-1, 0),
+            Self::NE => Hex::new(1, -1),
+            Self::NW => Hex::new(0, -1),
+            Self::SE => Hex::new(0, 1),
+            Self::SW => Hex::new(-1, 1),
+        }
+    }
+}
+
+impl FromStr for PointyDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "e" => Ok(Self::E),
+            "w" => Ok(Self::W),
+            "ne" => Ok(Self::NE),
+            "nw" => Ok(Self::NW),
+            "se" => Ok(Self::SE),
+            "sw" => Ok(Self::SW),
+            _ => Err(format!("invalid hex direction '{s}'")),
+        }
+    }
+}
+
+impl Add<PointyDirection> for Hex {
+    type Output = Self;
+
+    fn add(self, direction: PointyDirection) -> Self {
+        self + direction.offset()
+    }
+}
+
+/// Parses a path of directions, either separated (`ne,ne,s`) or run together
+/// (`esenee`), into [`FlatDirection`]s or [`PointyDirection`]s.
+///
+/// # Errors
+///
+/// Returns the offending text if part of the path is not a direction.
+pub fn parse_hex_path<D: FromStr>(path: &str) -> Result<Vec<D>, String> {
+    let mut directions = Vec::new();
+    let mut rest = path.trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+
+    while !rest.is_empty() {
+        // Two-letter directions first, so `ne` is not read as `n` then `e`
+        let (direction, len) = [2, 1]
+            .into_iter()
+            .find_map(|len| Some((rest.get(..len)?.parse().ok()?, len)))
+            .ok_or_else(|| format!("invalid hex direction at '{rest}'"))?;
+
+        directions.push(direction);
+        rest = rest[len..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    }
+
+    Ok(directions)
+}
+
+/// Draws hexes as text using their [`Hex::doubled`] coordinates, with spaces
+/// between and around them, over the bounding box of the hexes.
+pub fn render_hexes(cells: impl IntoIterator<Item = (Hex, char)>, layout: HexLayout) -> String {
+    let mut grid = SparseGrid::new(' ');
+    for (hex, glyph) in cells {
+        grid.set(hex.doubled(layout), glyph);
+    }
+
+    grid.render(|&glyph| glyph)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk<D: FromStr>(path: &str) -> Hex
+    where
+        Hex: Add<D, Output = Hex>,
+    {
+        parse_hex_path::<D>(path)
+            .unwrap()
+            .into_iter()
+            .fold(Hex::ORIGIN, |hex, step| hex + step)
+    }
+
+    #[test]
+    fn test_flat_paths() {
+        assert_eq!(walk::<FlatDirection>("ne,ne,ne").distance(Hex::ORIGIN), 3);
+        assert_eq!(
+            walk::<FlatDirection>("ne,ne,sw,sw").distance(Hex::ORIGIN),
+            0
+        );
+        assert_eq!(walk::<FlatDirection>("ne,ne,s,s").distance(Hex::ORIGIN), 2);
+        assert_eq!(
+            walk::<FlatDirection>("se,sw,se,sw,sw").distance(Hex::ORIGIN),
+            3
+        );
+    }
+
+    #[test]
+    fn test_pointy_paths() {
+        assert_eq!(walk::<PointyDirection>("nwwswee"), Hex::ORIGIN);
+        assert_eq!(walk::<PointyDirection>("esew"), Hex::new(0, 1));
+        assert!(parse_hex_path::<PointyDirection>("nex").is_err());
+        assert!(parse_hex_path::<PointyDirection>("n").is_err());
+    }
+
+    #[test]
+    fn test_neighbors_match_directions() {
+        let hex = Hex::new(2, -1);
+
+        for direction in FlatDirection::all() {
+            assert!(hex.neighbors().contains(&(hex + direction)));
+        }
+        for direction in PointyDirection::all() {
+            assert_eq!((hex + direction).distance(hex), 1);
+        }
+    }
+
+    #[test]
+    fn test_ring_and_within() {
+        let center = Hex::new(1, 1);
+
+        assert_eq!(center.ring(0), [center]);
+        for radius in 1..4 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(
+                ring.iter()
+                    .all(|hex| hex.distance(center) == i64::from(radius))
+            );
+        }
+        assert_eq!(center.within(2).count(), 19);
+    }
+
+    #[test]
+    fn test_cube() {
+        let hex = Hex::new(2, -3);
+        let cube = Cube::from(hex);
+
+        assert_eq!(cube, Cube::new(2, -3, 1));
+        assert_eq!(Hex::from(cube), hex);
+        assert_eq!(cube.distance(Cube::default()), hex.distance(Hex::ORIGIN));
+
+        let mut rotated = cube;
+        for _ in 0..6 {
+            rotated = rotated.rotate_clockwise();
+        }
+        assert_eq!(rotated, cube);
+        assert_eq!(cube.rotate_clockwise().rotate_counterclockwise(), cube);
+    }
+
+    #[test]
+    fn test_render() {
+        let cells = Hex::ORIGIN
+            .within(1)
+            .map(|hex| (hex, if hex == Hex::ORIGIN { 'o' } else { '#' }));
+
+        assert_eq!(
+            render_hexes(cells, HexLayout::Pointy),
+            " # # \n# o #\n # # "
+        );
+        assert_eq!(
+            Hex::new(1, 0).to_pixel(HexLayout::Flat),
+            (1.5, 3f64.sqrt() / 2.0)
+        );
+    }
+}
@@ -1,95 +1,381 @@
+pub mod debug;
 pub mod directions;
+pub mod error;
+pub mod grid;
+pub mod hex;
+pub mod panics;
+pub mod params;
 pub mod point3d;
 pub mod points;
+pub mod registry;
+pub mod spans;
+pub mod sparse_grid;
+pub mod testing;
+pub mod timer;
+pub mod visualize;
 
+pub use debug::*;
 pub use directions::*;
+pub use error::*;
+pub use grid::*;
+pub use hex::*;
+pub use panics::*;
+pub use params::*;
 pub use point3d::*;
 pub use points::*;
+pub use registry::*;
+pub use spans::*;
+pub use sparse_grid::*;
+pub use testing::*;
+pub use timer::*;
+pub use visualize::*;
 
+/// A day's solution. Each part has its own output type, so part 1 can return a
+/// number while part 2 returns e.g. a password or a coordinate. Puzzles without a
+/// second part (day 25) use `type Output2 = NoPart;` and return `Ok(NoPart)`.
+///
+/// Every step returns a [`SolutionError`] when it fails; a step that cannot fail
+/// wraps its answer in `Ok`. Parsing that depends on [`Solution::Params`] overrides
+/// `try_parse_input_with` as well. The infallible shorthands for tests (`part1`,
+/// `parse_input_with`, ...) come from [`SolutionExt`].
 pub trait Solution {
     type Input;
-    type Output: std::fmt::Display;
+    type Output1: std::fmt::Display + 'static;
+    type Output2: std::fmt::Display + 'static;
+    /// Constants that differ between the example and the real input, `()` if none.
+    /// `Default` gives the real input's values. See [`params!`].
+    ///
+    /// Only parsing receives them: copy whatever the parts need into the parsed
+    /// input, so the parts keep their signatures and tests that pass the example's
+    /// values through [`SolutionExt::parse_input_with`] reach both parts:
+    ///
+    /// ```ignore
+    /// utils::params! {
+    ///     pub struct Params {
+    ///         size: i32 = 71,
+    ///         steps: usize = 1024,
+    ///     }
+    /// }
+    ///
+    /// pub struct Memory {
+    ///     size: i32,
+    ///     bytes: Vec<Point<i32>>,
+    /// }
+    ///
+    /// impl Solution for Day18 {
+    ///     type Input = Memory;
+    ///     type Params = Params;
+    ///     // ...
+    ///
+    ///     fn try_parse_input(&self, input: &str) -> Result<Memory, SolutionError> {
+    ///         self.try_parse_input_with(input, &Params::default())
+    ///     }
+    ///
+    ///     fn try_parse_input_with(&self, input: &str, params: &Params) -> Result<Memory, SolutionError> {
+    ///         let bytes = try_parse_lines(input)?;
+    ///         Ok(Memory {
+    ///             size: params.size,
+    ///             bytes: bytes.into_iter().take(params.steps).collect(),
+    ///         })
+    ///     }
+    ///
+    ///     fn try_part1(&self, memory: &Memory) -> Result<Self::Output1, SolutionError> {
+    ///         Ok(shortest_path(&memory.bytes, memory.size))
+    ///     }
+    /// }
+    /// ```
+    type Params: PuzzleParams;
 
-    fn parse_input(&self, input: &str) -> Self::Input;
-    fn part1(&self, input: &Self::Input) -> Self::Output;
-    fn part2(&self, input: &Self::Input) -> Self::Output;
+    fn try_parse_input(&self, input: &str) -> Result<Self::Input, SolutionError>;
 
-    fn solve(&self, input: &str) {
-        let parsed = self.parse_input(input);
+    /// Parses with the given parameters. Defaults to [`Solution::try_parse_input`],
+    /// which ignores them.
+    fn try_parse_input_with(
+        &self,
+        input: &str,
+        _params: &Self::Params,
+    ) -> Result<Self::Input, SolutionError> {
+        self.try_parse_input(input)
+    }
+
+    fn try_part1(&self, input: &Self::Input) -> Result<Self::Output1, SolutionError>;
+
+    fn try_part2(&self, input: &Self::Input) -> Result<Self::Output2, SolutionError>;
+
+    /// Solves both parts in one go. Override this when part 2 needs something part 1
+    /// computed (a path, a loop, ...) instead of recomputing it, and call
+    /// [`PartTimer::part1_done_with`] once part 1's answer is known so timing mode can
+    /// attribute the time to each part, and the answer is kept if part 2 fails.
+    /// `--part2` runs still use [`Solution::try_part2`].
+    fn try_solve_both(
+        &self,
+        input: &Self::Input,
+        timer: &mut PartTimer,
+    ) -> Result<(Self::Output1, Self::Output2), SolutionError> {
+        let result1 = self.try_part1(input)?;
+        timer.part1_done_with(&result1);
+        let result2 = self.try_part2(input)?;
 
-        println!("Part 1: {}", self.part1(&parsed));
-        println!("Part 2: {}", self.part2(&parsed));
+        Ok((result1, result2))
     }
 
-    fn solve_timed(&self, input: &str) -> (std::time::Duration, std::time::Duration) {
-        use std::time::Instant;
+    /// Parses and solves both parts. A part that fails or panics does not stop the
+    /// other; see [`catch_panic`].
+    fn solve(&self, input: &str, params: &Self::Params) -> Result<(), SolutionError> {
+        let parsed = catch_panic(|| self.try_parse_input_with(input, params))
+            .map_err(|err| err.in_stage("parse"))?;
 
-        let start1 = Instant::now();
-        let parsed = self.parse_input(input);
-        let result1 = self.part1(&parsed);
-        let time1 = start1.elapsed();
+        let parts = solve_parts(self, &parsed, PartTimer::start());
 
-        let start2 = Instant::now();
-        let result2 = self.part2(&parsed);
-        let time2 = start2.elapsed();
+        if let Ok(answer1) = &parts.answer1 {
+            println!("Part 1: {answer1}");
+        }
+        if let Ok(answer2) = &parts.answer2 {
+            println!("Part 2: {answer2}");
+        }
 
-        println!("Part 1: {} ({}ms)", result1, time1.as_secs_f64() * 1000.0);
-        println!("Part 2: {} ({}ms)", result2, time2.as_secs_f64() * 1000.0);
-        println!("TIMING:{}:{}", time1.as_micros(), time2.as_micros());
+        report_failures(input, [parts.answer1.err(), parts.answer2.err()])
+    }
 
-        (time1, time2)
+    fn solve_timed(
+        &self,
+        input: &str,
+        params: &Self::Params,
+    ) -> Result<(std::time::Duration, std::time::Duration), SolutionError> {
+        let timer = PartTimer::start();
+        let parsed = {
+            let _s = span("parse");
+            catch_panic(|| self.try_parse_input_with(input, params))
+                .map_err(|err| err.in_stage("parse"))?
+        };
+        let parts = solve_parts(self, &parsed, timer);
+        let (time1, time2) = (parts.time1, parts.time2);
+
+        // The `TIMING:` line has `-` for a missing part 2 and `failed` for a failed part
+        let timing1 = match &parts.answer1 {
+            Ok(answer1) => {
+                println!("Part 1: {} ({}ms)", answer1, time1.as_secs_f64() * 1000.0);
+                time1.as_micros().to_string()
+            }
+            Err(_) => "failed".to_string(),
+        };
+        let timing2 = match &parts.answer2 {
+            Ok(answer2) if !has_part2::<Self>() => {
+                println!("Part 2: {answer2}");
+                "-".to_string()
+            }
+            Ok(answer2) => {
+                println!("Part 2: {} ({}ms)", answer2, time2.as_secs_f64() * 1000.0);
+                time2.as_micros().to_string()
+            }
+            Err(_) => "failed".to_string(),
+        };
+        println!("TIMING:{timing1}:{timing2}");
+
+        report_failures(input, [parts.answer1.err(), parts.answer2.err()])?;
+
+        Ok((time1, time2))
     }
 
-    fn solve_part1_timed(&self, input: &str) -> std::time::Duration {
+    fn solve_part1_timed(
+        &self,
+        input: &str,
+        params: &Self::Params,
+    ) -> Result<std::time::Duration, SolutionError> {
         use std::time::Instant;
 
+        let span1 = span("part 1");
         let start = Instant::now();
-        let parsed = self.parse_input(input);
-        let result = self.part1(&parsed);
+        let parsed = {
+            let _s = span("parse");
+            catch_panic(|| self.try_parse_input_with(input, params))
+                .map_err(|err| err.in_stage("parse"))?
+        };
+        let result =
+            catch_panic(|| self.try_part1(&parsed)).map_err(|err| err.in_stage("part 1"))?;
         let elapsed = start.elapsed();
+        drop(span1);
 
         println!("Part 1: {} ({}ms)", result, elapsed.as_secs_f64() * 1000.0);
         println!("TIMING_PART1:{}", elapsed.as_micros());
 
-        elapsed
+        Ok(elapsed)
     }
 
-    fn solve_part2_timed(&self, input: &str) -> std::time::Duration {
+    fn solve_part2_timed(
+        &self,
+        input: &str,
+        params: &Self::Params,
+    ) -> Result<std::time::Duration, SolutionError> {
         use std::time::Instant;
 
+        if !has_part2::<Self>() {
+            println!("Part 2: {NoPart}");
+            println!("TIMING_PART2:-");
+            return Ok(std::time::Duration::ZERO);
+        }
+
+        let span2 = span("part 2");
         let start = Instant::now();
-        let parsed = self.parse_input(input);
-        let result = self.part2(&parsed);
+        let parsed = {
+            let _s = span("parse");
+            catch_panic(|| self.try_parse_input_with(input, params))
+                .map_err(|err| err.in_stage("parse"))?
+        };
+        let result =
+            catch_panic(|| self.try_part2(&parsed)).map_err(|err| err.in_stage("part 2"))?;
         let elapsed = start.elapsed();
+        drop(span2);
 
         println!("Part 2: {} ({}ms)", result, elapsed.as_secs_f64() * 1000.0);
         println!("TIMING_PART2:{}", elapsed.as_micros());
 
-        elapsed
+        Ok(elapsed)
     }
 }
 
+/// The part 2 answer of a puzzle that has no part 2, shown as `—`. Timing and
+/// analytics report the part as absent instead of measuring it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoPart;
+
+impl std::fmt::Display for NoPart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("—")
+    }
+}
+
+/// Whether the solution has a part 2, i.e. its `Output2` is not [`NoPart`].
+pub fn has_part2<S: Solution + ?Sized>() -> bool {
+    std::any::TypeId::of::<S::Output2>() != std::any::TypeId::of::<NoPart>()
+}
+
+/// Infallible shorthands for every [`Solution`], for tests and quick experiments.
+/// A failing step panics with its error.
+pub trait SolutionExt: Solution {
+    fn parse_input(&self, input: &str) -> Self::Input {
+        self.parse_input_with(input, &Self::Params::default())
+    }
+
+    /// Parses with explicit parameters, e.g. the example's values in a test.
+    fn parse_input_with(&self, input: &str, params: &Self::Params) -> Self::Input {
+        self.try_parse_input_with(input, params)
+            .unwrap_or_else(|err| panic!("{}", err.in_stage("parse")))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Self::Output1 {
+        self.try_part1(input)
+            .unwrap_or_else(|err| panic!("{}", err.in_stage("part 1")))
+    }
+
+    fn part2(&self, input: &Self::Input) -> Self::Output2 {
+        self.try_part2(input)
+            .unwrap_or_else(|err| panic!("{}", err.in_stage("part 2")))
+    }
+}
+
+impl<S: Solution + ?Sized> SolutionExt for S {}
+
 #[macro_export]
 macro_rules! run_solution {
     ($solution:expr) => {{
-        let args: Vec<String> = std::env::args().collect();
-        let mode = args.get(1).map(String::as_str);
+        let args: Vec<String> = std::env::args().skip(1).collect();
+
+        if args.iter().any(|arg| arg == "--spans") {
+            $crate::enable_spans();
+        }
+
+        if args.iter().any(|arg| arg == "--visualize") {
+            $crate::enable_visualization();
+        }
 
-        let input = include_str!("../input.txt");
+        let mode = args
+            .iter()
+            .map(String::as_str)
+            .find(|arg| matches!(*arg, "--timing" | "--part1" | "--part2"));
 
-        match mode {
-            Some("--timing") => {
-                $solution.solve_timed(input);
+        // Debug output would slow timing runs down and could garble their output
+        if mode.is_some() {
+            $crate::set_debug_level(0);
+        } else if let Some(level) = $crate::debug_level_from_args(&args) {
+            $crate::set_debug_level(level);
+        }
+
+        // `--input <path>` reads another input at runtime instead of the embedded one
+        let input_path = args
+            .iter()
+            .position(|arg| arg == "--input")
+            .and_then(|index| args.get(index + 1));
+        let owned_input;
+        let input: &str = match input_path {
+            Some(path) => {
+                owned_input = std::fs::read_to_string(path)
+                    .unwrap_or_else(|err| panic!("Failed to read {path}: {err}"));
+                &owned_input
             }
+            None => include_str!("../input.txt"),
+        };
+
+        let solution = $solution;
+        let result = $crate::params_from_args(&solution, &args).and_then(|params| match mode {
+            Some("--timing") => $crate::Solution::solve_timed(&solution, input, &params).map(drop),
             Some("--part1") => {
-                $solution.solve_part1_timed(input);
+                $crate::Solution::solve_part1_timed(&solution, input, &params).map(drop)
             }
             Some("--part2") => {
-                $solution.solve_part2_timed(input);
-            }
-            _ => {
-                $solution.solve(input);
+                $crate::Solution::solve_part2_timed(&solution, input, &params).map(drop)
             }
+            _ => $crate::Solution::solve(&solution, input, &params),
+        });
+
+        $crate::report_spans();
+
+        if let Err(err) = result {
+            $crate::report_error(&err, input);
+            std::process::exit(1);
         }
     }};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Doubled;
+
+    impl Solution for Doubled {
+        type Input = u32;
+        type Output1 = u32;
+        type Output2 = NoPart;
+        type Params = ();
+
+        fn try_parse_input(&self, input: &str) -> Result<Self::Input, SolutionError> {
+            input
+                .trim()
+                .parse()
+                .map_err(|err| SolutionError::new(format!("{err}")))
+        }
+
+        fn try_part1(&self, input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+            Ok(input * 2)
+        }
+
+        fn try_part2(&self, _input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+            Ok(NoPart)
+        }
+    }
+
+    #[test]
+    fn test_infallible_shorthands() {
+        let input = Doubled.parse_input("21\n");
+        assert_eq!(Doubled.part1(&input), 42);
+        assert_eq!(Doubled.part2(&input), NoPart);
+        assert!(!has_part2::<Doubled>());
+    }
+
+    #[test]
+    fn test_shorthand_panics_with_the_error() {
+        let err = catch_panic(|| Ok(Doubled.parse_input("x"))).unwrap_err();
+        assert!(err.message().contains("parse: invalid digit"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Panic isolation for `run_solution!`: a panic in one part is reported with its
+//! message and location, and the other part still runs.
+//!
+//! Panics can only be caught when they unwind. In a profile with `panic = "abort"`,
+//! per-part isolation does not work: the first panic is printed as usual and ends
+//! the run.
+
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use crate::{PartTimer, Solution, SolutionError, span};
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static CAUGHT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning a panic into a [`SolutionError`] with the panic's message and
+/// location. Under `panic = "abort"` the panic ends the process instead.
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T, SolutionError>) -> Result<T, SolutionError> {
+    install_hook();
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+
+    result.unwrap_or_else(|_| {
+        let message = CAUGHT.take().unwrap_or_else(|| "panicked".to_string());
+        Err(SolutionError::from_panic(message))
+    })
+}
+
+/// Records the message of panics caught by [`catch_panic`] instead of printing it;
+/// other panics are printed as usual. When panics abort, nothing can be caught, so
+/// every panic is printed before the process dies.
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() || cfg!(panic = "abort") {
+                default_hook(info);
+                return;
+            }
+
+            let message = info.payload_as_str().unwrap_or("Box<dyn Any>");
+            let description = match info.location() {
+                Some(location) => format!(
+                    "panicked at {}:{}:{}: {message}",
+                    location.file(),
+                    location.line(),
+                    location.column()
+                ),
+                None => format!("panicked: {message}"),
+            };
+            CAUGHT.set(Some(description));
+        }));
+    });
+}
+
+/// The answers of both parts, and the time each took. The time of a failed part
+/// means nothing.
+pub(crate) struct SolvedParts {
+    pub answer1: Result<String, SolutionError>,
+    pub answer2: Result<String, SolutionError>,
+    pub time1: Duration,
+    pub time2: Duration,
+}
+
+/// Solves both parts with [`Solution::try_solve_both`]. If one part fails or panics,
+/// the other still gets its answer:
+///
+/// - if part 1 failed, part 2 is run on its own and timed separately;
+/// - if part 2 failed, part 1's answer is the one kept by
+///   [`PartTimer::part1_done_with`], with its time from the first run. An override
+///   that only calls [`PartTimer::part1_done`] loses it, so part 1 is run again,
+///   untimed.
+pub(crate) fn solve_parts<S: Solution + ?Sized>(
+    solution: &S,
+    input: &S::Input,
+    mut timer: PartTimer,
+) -> SolvedParts {
+    let result = catch_panic(|| solution.try_solve_both(input, &mut timer));
+    let stage = timer.stage();
+    let part1_answer = timer.take_part1_answer();
+    let (time1, time2) = timer.finish();
+
+    let (answer1, answer2, time2) = match result {
+        Ok((result1, result2)) => (Ok(result1.to_string()), Ok(result2.to_string()), time2),
+        Err(err) if stage == "part 2" => {
+            let answer1 = part1_answer.map_or_else(
+                || {
+                    catch_panic(|| solution.try_part1(input))
+                        .map(|result1| result1.to_string())
+                        .map_err(|err| err.in_stage("part 1"))
+                },
+                Ok,
+            );
+            (answer1, Err(err.in_stage("part 2")), time2)
+        }
+        Err(err) => {
+            let span2 = span("part 2");
+            let start = Instant::now();
+            let answer2 = catch_panic(|| solution.try_part2(input))
+                .map(|result2| result2.to_string())
+                .map_err(|err| err.in_stage("part 2"));
+            let time2 = start.elapsed();
+            drop(span2);
+
+            (Err(err.in_stage("part 1")), answer2, time2)
+        }
+    };
+
+    SolvedParts {
+        answer1,
+        answer2,
+        time1,
+        time2,
+    }
+}
+
+/// Reports every failed part on its own, so one part's failure does not hide the
+/// other's. A single failure is returned as is.
+pub(crate) fn report_failures(
+    input: &str,
+    failures: [Option<SolutionError>; 2],
+) -> Result<(), SolutionError> {
+    match failures {
+        [None, None] => Ok(()),
+        [Some(failure), None] | [None, Some(failure)] => Err(failure),
+        [Some(first), Some(second)] => {
+            report_error(&first, input);
+            report_error(&second, input);
+            Err(SolutionError::new("both parts failed"))
+        }
+    }
+}
+
+/// Prints a failure: the report on stderr and, for a panic, a
+/// `PANIC:<stage>:<message>` line on stdout for the CLI. Used by `run_solution!`.
+pub fn report_error(err: &SolutionError, input: &str) {
+    eprintln!("{}", err.report(input));
+
+    if err.is_panic() {
+        println!(
+            "PANIC:{}:{}",
+            err.stage().unwrap_or("solve"),
+            err.message().replace('\n', " ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_panic() {
+        assert_eq!(catch_panic(|| Ok(1)), Ok(1));
+
+        let err = catch_panic::<()>(|| panic!("no start tile")).unwrap_err();
+        assert!(err.is_panic());
+        assert!(err.message().starts_with("panicked at "));
+        assert!(err.message().ends_with(": no start tile"));
+    }
+
+    struct Flaky;
+
+    impl Solution for Flaky {
+        type Input = ();
+        type Output1 = u32;
+        type Output2 = u32;
+        type Params = ();
+
+        fn try_parse_input(&self, _input: &str) -> Result<Self::Input, SolutionError> {
+            Ok(())
+        }
+
+        fn try_part1(&self, _input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+            panic!("part 1 is broken")
+        }
+
+        fn try_part2(&self, _input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+            Ok(2)
+        }
+    }
+
+    #[test]
+    fn test_panicking_part_does_not_stop_the_other() {
+        let parts = solve_parts(&Flaky, &(), PartTimer::start());
+
+        let err = parts.answer1.unwrap_err();
+        assert_eq!(err.stage(), Some("part 1"));
+        assert!(err.message().ends_with("part 1 is broken"));
+        assert_eq!(parts.answer2, Ok("2".to_string()));
+    }
+
+    struct Counting {
+        part1_runs: Cell<u32>,
+    }
+
+    impl Solution for Counting {
+        type Input = ();
+        type Output1 = u32;
+        type Output2 = u32;
+        type Params = ();
+
+        fn try_parse_input(&self, _input: &str) -> Result<Self::Input, SolutionError> {
+            Ok(())
+        }
+
+        fn try_part1(&self, _input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+            self.part1_runs.set(self.part1_runs.get() + 1);
+            Ok(1)
+        }
+
+        fn try_part2(&self, _input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+            panic!("part 2 is broken")
+        }
+    }
+
+    #[test]
+    fn test_failing_part2_keeps_part1_without_rerunning_it() {
+        let solution = Counting {
+            part1_runs: Cell::new(0),
+        };
+        let parts = solve_parts(&solution, &(), PartTimer::start());
+
+        assert_eq!(parts.answer1, Ok("1".to_string()));
+        assert_eq!(parts.answer2.unwrap_err().stage(), Some("part 2"));
+        assert_eq!(solution.part1_runs.get(), 1);
+    }
+}
@@ -0,0 +1,135 @@
+//! Puzzle constants that differ between the example and the real input, such as a
+//! grid size of 7 vs 71 or 10 steps vs 1000, declared with [`params!`](crate::params).
+//!
+//! Defaults are the real input's values; tests pass the example's values to
+//! `parse_input_with`, and `run_solution!` accepts `--param size=7` overrides.
+
+use crate::{Solution, SolutionError};
+
+/// Parameters that can be overridden by name from the command line.
+pub trait PuzzleParams: Default {
+    /// Sets one parameter from its `key=value` form.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String>;
+}
+
+/// For days without parameters.
+impl PuzzleParams for () {
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), String> {
+        Err(format!(
+            "unknown parameter '{key}', this day has no parameters"
+        ))
+    }
+}
+
+/// Declares a parameters struct whose `Default` holds the real input's values and
+/// which implements [`PuzzleParams`] for every field. Fields are private unless
+/// declared `pub`, as in a plain struct.
+///
+/// Only parsing receives the parameters ([`Solution::try_parse_input_with`]), not the
+/// parts: whatever the parts need is copied into the parsed input, as in the
+/// example on [`Solution::Params`].
+///
+/// ```ignore
+/// utils::params! {
+///     pub struct Params {
+///         size: usize = 71,
+///         pub steps: usize = 1024,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $($field_vis:vis $field:ident : $ty:ty = $default:expr),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis struct $name {
+            $($field_vis $field: $ty,)*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $crate::PuzzleParams for $name {
+            fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+                match key {
+                    $(stringify!($field) => {
+                        self.$field = value
+                            .parse()
+                            .map_err(|err| format!("invalid value '{value}' for {key}: {err}"))?;
+                        Ok(())
+                    })*
+                    _ => Err(format!("unknown parameter '{key}'")),
+                }
+            }
+        }
+    };
+}
+
+/// Builds a solution's parameters from its defaults and any `--param key=value`
+/// command-line arguments. Used by `run_solution!`.
+pub fn params_from_args<S: Solution>(
+    _solution: &S,
+    args: &[String],
+) -> Result<S::Params, SolutionError> {
+    let mut params = S::Params::default();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg != "--param" {
+            continue;
+        }
+
+        let assignment = args
+            .next()
+            .ok_or_else(|| SolutionError::new("--param needs a key=value argument"))?;
+        let (key, value) = assignment.split_once('=').ok_or_else(|| {
+            SolutionError::new(format!("expected --param key=value, got '{assignment}'"))
+        })?;
+
+        params
+            .set(key.trim(), value.trim())
+            .map_err(SolutionError::new)?;
+    }
+
+    Ok(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::params! {
+        struct TestParams {
+            size: usize = 71,
+            pub name: String = "real".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_params_defaults_and_overrides() {
+        let mut params = TestParams::default();
+        assert_eq!(params.size, 71);
+        assert_eq!(params.name, "real");
+
+        params.set("size", "7").unwrap();
+        assert_eq!(params.size, 7);
+
+        assert!(params.set("size", "seven").is_err());
+        assert!(params.set("steps", "10").is_err());
+    }
+
+    #[test]
+    fn test_unit_params_reject_everything() {
+        assert!(().set("size", "7").is_err());
+    }
+}
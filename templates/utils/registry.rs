@@ -0,0 +1,223 @@
+//! Days linked into the workspace's `runner` crate, which runs every day in one
+//! process for `aoc-cli run --all`.
+//!
+//! Each day's `lib.rs` registers its solution:
+//!
+//! ```ignore
+//! utils::register_day!(Day05);
+//! ```
+//!
+//! and the generated runner passes the registered days to [`run_days`]. `aoc-cli add`
+//! and `aoc-cli remove` keep the runner's list in sync.
+
+use std::time::Duration;
+
+use crate::panics::solve_parts;
+use crate::{
+    PartTimer, Solution, SolutionError, catch_panic, has_part2, report_error, set_debug_level,
+};
+
+/// A day's solution and its embedded input, declared by [`register_day!`](crate::register_day).
+pub struct RegisteredDay {
+    pub input: &'static str,
+    pub run: fn(&str) -> Result<DayResult, SolutionError>,
+}
+
+/// The answers of one day and the time each part took. A part that failed or
+/// panicked has its error instead of an answer, and its time means nothing.
+pub struct DayResult {
+    pub part1: Result<String, SolutionError>,
+    pub part2: Result<String, SolutionError>,
+    pub time1: Duration,
+    /// `None` for puzzles without a part 2.
+    pub time2: Option<Duration>,
+}
+
+/// Declares the day's `pub static DAY` for the workspace runner.
+#[macro_export]
+macro_rules! register_day {
+    ($solution:expr) => {
+        /// This day's solution, for the workspace runner.
+        pub static DAY: $crate::RegisteredDay = $crate::RegisteredDay {
+            input: include_str!("../input.txt"),
+            run: |input| $crate::run_registered(&$solution, input),
+        };
+    };
+}
+
+/// Solves both parts with the default parameters, timed the same way as `--timing`.
+/// A panic is caught like in `run_solution!`: a failing part does not stop the other
+/// part, and a failing parse does not stop the other days.
+pub fn run_registered<S: Solution>(solution: &S, input: &str) -> Result<DayResult, SolutionError> {
+    let timer = PartTimer::start();
+    let parsed = catch_panic(|| solution.try_parse_input_with(input, &S::Params::default()))
+        .map_err(|err| err.in_stage("parse"))?;
+    let parts = solve_parts(solution, &parsed, timer);
+
+    Ok(DayResult {
+        part1: parts.answer1,
+        part2: parts.answer2,
+        time1: parts.time1,
+        time2: has_part2::<S>().then_some(parts.time2),
+    })
+}
+
+/// Runs the given days in order. Each day's answers and timing are printed after a
+/// `DAY:<n>` line in the same format as `--timing`, each failure as an
+/// `ERROR:<message>` line (plus `PANIC:<stage>:<message>` for a panic), and the
+/// combined time of every part that succeeded as a final `TOTAL:<micros>` line.
+/// Debug output is turned off, as in other timing runs.
+pub fn run_days(days: &[(u8, &RegisteredDay)]) {
+    set_debug_level(0);
+
+    let mut total = Duration::ZERO;
+
+    for (day, registered) in days {
+        println!("DAY:{day}");
+
+        let result = match (registered.run)(registered.input) {
+            Ok(result) => result,
+            Err(err) => {
+                report_day_error(*day, &err, registered.input);
+                continue;
+            }
+        };
+
+        let timing1 = match &result.part1 {
+            Ok(answer1) => {
+                println!(
+                    "Part 1: {answer1} ({}ms)",
+                    result.time1.as_secs_f64() * 1000.0
+                );
+                total += result.time1;
+                result.time1.as_micros().to_string()
+            }
+            Err(_) => "failed".to_string(),
+        };
+        let timing2 = match (&result.part2, result.time2) {
+            (Ok(answer2), None) => {
+                println!("Part 2: {answer2}");
+                "-".to_string()
+            }
+            (Ok(answer2), Some(time2)) => {
+                println!("Part 2: {answer2} ({}ms)", time2.as_secs_f64() * 1000.0);
+                total += time2;
+                time2.as_micros().to_string()
+            }
+            (Err(_), _) => "failed".to_string(),
+        };
+        println!("TIMING:{timing1}:{timing2}");
+
+        for err in [result.part1.err(), result.part2.err()]
+            .into_iter()
+            .flatten()
+        {
+            report_day_error(*day, &err, registered.input);
+        }
+    }
+
+    println!("TOTAL:{}", total.as_micros());
+}
+
+fn report_day_error(day: u8, err: &SolutionError, input: &str) {
+    println!("ERROR:{}", err.to_string().replace('\n', " "));
+    eprint!("day {day}: ");
+    report_error(err, input);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Location, NoPart, try_parse_lines};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Output1 = u32;
+        type Output2 = usize;
+        type Params = ();
+
+        fn try_parse_input(&self, input: &str) -> Result<Self::Input, SolutionError> {
+            try_parse_lines(input)
+        }
+
+        fn try_part1(&self, input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+            Ok(input.iter().sum())
+        }
+
+        fn try_part2(&self, input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+            Ok(input.len())
+        }
+    }
+
+    #[test]
+    fn test_run_registered() {
+        let result = run_registered(&Sum, "1\n2\n3").unwrap();
+        assert_eq!(result.part1, Ok("6".to_string()));
+        assert_eq!(result.part2, Ok("3".to_string()));
+
+        let err = run_registered(&Sum, "1\nx").err().unwrap();
+        assert_eq!(err.location(), Some(Location { line: 2, column: 1 }));
+    }
+
+    struct SinglePart;
+
+    impl Solution for SinglePart {
+        type Input = usize;
+        type Output1 = usize;
+        type Output2 = NoPart;
+        type Params = ();
+
+        fn try_parse_input(&self, input: &str) -> Result<Self::Input, SolutionError> {
+            Ok(input.len())
+        }
+
+        fn try_part1(&self, input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+            Ok(*input)
+        }
+
+        fn try_part2(&self, _input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+            Ok(NoPart)
+        }
+    }
+
+    #[test]
+    fn test_run_registered_without_part2() {
+        let result = run_registered(&SinglePart, "abc").unwrap();
+        assert_eq!(result.part1, Ok("3".to_string()));
+        assert_eq!(result.part2, Ok("—".to_string()));
+        assert_eq!(result.time2, None);
+    }
+
+    struct BrokenPart2;
+
+    impl Solution for BrokenPart2 {
+        type Input = ();
+        type Output1 = u32;
+        type Output2 = u32;
+        type Params = ();
+
+        fn try_parse_input(&self, _input: &str) -> Result<Self::Input, SolutionError> {
+            Ok(())
+        }
+
+        fn try_part1(&self, _input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+            Ok(1)
+        }
+
+        fn try_part2(&self, _input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+            panic!("part 2 is broken")
+        }
+    }
+
+    #[test]
+    fn test_run_registered_keeps_the_other_part() {
+        let result = run_registered(&BrokenPart2, "").unwrap();
+        assert_eq!(result.part1, Ok("1".to_string()));
+
+        let err = result.part2.unwrap_err();
+        assert!(err.is_panic());
+        assert_eq!(err.stage(), Some("part 2"));
+    }
+}
@@ -0,0 +1,192 @@
+//! Lightweight scoped spans for finding the slow step inside a solution.
+//!
+//! ```ignore
+//! fn try_part2(&self, input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+//!     let _s = utils::span("build graph");
+//!     let graph = build_graph(input);
+//!     drop(_s);
+//!
+//!     let _s = utils::span("bfs");
+//!     Ok(bfs(&graph))
+//! }
+//! ```
+//!
+//! Spans do nothing unless the solution runs under `aoc-cli time --spans`, in which
+//! case nested timings are collected on the main thread and reported to the CLI.
+
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+struct Node {
+    name: &'static str,
+    parent: Option<usize>,
+    depth: usize,
+    elapsed: Duration,
+    calls: u32,
+}
+
+#[derive(Default)]
+struct Tree {
+    nodes: Vec<Node>,
+    stack: Vec<usize>,
+}
+
+thread_local! {
+    // Only the main thread's spans are reported, so other threads never collect any
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static TREE: RefCell<Tree> = RefCell::new(Tree::default());
+}
+
+/// Guard returned by [`span`]; the span ends when it is dropped.
+#[must_use = "the span ends as soon as the guard is dropped"]
+pub struct Span {
+    active: Option<(usize, Instant)>,
+}
+
+/// Starts a named span that lasts until the returned guard is dropped.
+///
+/// Spans with the same name under the same parent (e.g. inside a loop) are merged,
+/// adding up their time and counting the calls.
+pub fn span(name: &'static str) -> Span {
+    if !spans_enabled() {
+        return Span { active: None };
+    }
+
+    let index = TREE.with_borrow_mut(|tree| {
+        let parent = tree.stack.last().copied();
+        let existing = tree
+            .nodes
+            .iter()
+            .position(|node| node.parent == parent && node.name == name);
+
+        let index = existing.unwrap_or_else(|| {
+            tree.nodes.push(Node {
+                name,
+                parent,
+                depth: tree.stack.len(),
+                elapsed: Duration::ZERO,
+                calls: 0,
+            });
+            tree.nodes.len() - 1
+        });
+
+        tree.stack.push(index);
+        index
+    });
+
+    Span {
+        active: Some((index, Instant::now())),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let Some((index, start)) = self.active else {
+            return;
+        };
+        let elapsed = start.elapsed();
+
+        TREE.with_borrow_mut(|tree| {
+            let node = &mut tree.nodes[index];
+            node.elapsed += elapsed;
+            node.calls += 1;
+
+            if let Some(position) = tree.stack.iter().rposition(|&open| open == index) {
+                tree.stack.truncate(position);
+            }
+        });
+    }
+}
+
+/// Turns span collection on. Called by `run_solution!` when given `--spans`.
+pub fn enable_spans() {
+    ENABLED.set(true);
+}
+
+#[must_use]
+pub fn spans_enabled() -> bool {
+    ENABLED.get()
+}
+
+/// Prints the collected spans for the CLI, one `SPAN:<depth>:<calls>:<micros>:<name>`
+/// line per span in tree order. Does nothing if spans are disabled.
+pub fn report_spans() {
+    for line in span_lines() {
+        println!("{line}");
+    }
+}
+
+fn span_lines() -> Vec<String> {
+    if !spans_enabled() {
+        return Vec::new();
+    }
+
+    TREE.with_borrow(|tree| {
+        let mut order = Vec::with_capacity(tree.nodes.len());
+        push_children(tree, None, &mut order);
+
+        order
+            .into_iter()
+            .map(|index| {
+                let node = &tree.nodes[index];
+                format!(
+                    "SPAN:{}:{}:{}:{}",
+                    node.depth,
+                    node.calls,
+                    node.elapsed.as_micros(),
+                    node.name
+                )
+            })
+            .collect()
+    })
+}
+
+fn push_children(tree: &Tree, parent: Option<usize>, order: &mut Vec<usize>) {
+    for (index, node) in tree.nodes.iter().enumerate() {
+        if node.parent == parent {
+            order.push(index);
+            push_children(tree, Some(index), order);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_span_records_nothing() {
+        assert!(!spans_enabled());
+
+        {
+            let _s = span("x");
+        }
+
+        TREE.with_borrow(|tree| assert!(tree.nodes.is_empty()));
+        assert!(span_lines().is_empty());
+    }
+
+    #[test]
+    fn test_nested_spans_are_merged() {
+        enable_spans();
+
+        {
+            let _outer = span("outer");
+            for _ in 0..3 {
+                let _inner = span("inner");
+            }
+        }
+
+        TREE.with_borrow(|tree| {
+            assert_eq!(tree.nodes.len(), 2);
+            assert_eq!(tree.nodes[0].name, "outer");
+            assert_eq!(tree.nodes[0].calls, 1);
+            assert_eq!(tree.nodes[1].name, "inner");
+            assert_eq!(tree.nodes[1].parent, Some(0));
+            assert_eq!(tree.nodes[1].depth, 1);
+            assert_eq!(tree.nodes[1].calls, 3);
+            assert!(tree.stack.is_empty());
+        });
+        assert_eq!(span_lines().len(), 2);
+    }
+}
@@ -0,0 +1,309 @@
+//! An unbounded grid for puzzles where the world grows: falling sand, Game of Life
+//! variants, infinite image enhancement.
+//!
+//! ```ignore
+//! let mut cells = SparseGrid::parse(input, false, |c| c == '#');
+//! let next = cells.bounds().unwrap().pad(1).points().filter(|&pos| {
+//!     let alive = cells.all_neighbors(pos).filter(|&n| cells[n]).count();
+//!     alive == 3 || (cells[pos] && alive == 2)
+//! });
+//! ```
+//!
+//! Only the cells that differ from the default value are stored, so positions may
+//! be anywhere, negative ones included. `y` grows downwards, as in [`crate::Grid`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Index;
+
+use crate::{Direction, GridCell, Point};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point<i64>, T>,
+    default: T,
+}
+
+/// The smallest rectangle holding every occupied cell of a [`SparseGrid`], with
+/// both corners included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds {
+    pub min: Point<i64>,
+    pub max: Point<i64>,
+}
+
+impl Bounds {
+    #[must_use]
+    pub const fn width(&self) -> i64 {
+        self.max.x - self.min.x + 1
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> i64 {
+        self.max.y - self.min.y + 1
+    }
+
+    #[must_use]
+    pub const fn contains(&self, pos: Point<i64>) -> bool {
+        pos.x >= self.min.x && pos.x <= self.max.x && pos.y >= self.min.y && pos.y <= self.max.y
+    }
+
+    /// The bounds grown by `amount` on every side, e.g. to take in the cells a
+    /// simulation step can reach.
+    #[must_use]
+    pub fn pad(&self, amount: i64) -> Self {
+        Self {
+            min: Point::new(self.min.x - amount, self.min.y - amount),
+            max: Point::new(self.max.x + amount, self.max.y + amount),
+        }
+    }
+
+    /// Every position inside, in reading order.
+    pub fn points(&self) -> impl Iterator<Item = Point<i64>> + use<> {
+        let Self { min, max } = *self;
+        (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+impl<T> SparseGrid<T> {
+    /// An empty grid: every cell holds `default`.
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    /// The value of every cell that is not occupied.
+    pub const fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    /// Number of occupied cells.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn is_occupied(&self, pos: impl Into<Point<i64>>) -> bool {
+        self.cells.contains_key(&pos.into())
+    }
+
+    /// The cell at `pos`, which is the default value unless it was set.
+    pub fn get(&self, pos: impl Into<Point<i64>>) -> &T {
+        self.cells.get(&pos.into()).unwrap_or(&self.default)
+    }
+
+    /// Clears the cell at `pos` back to the default value, returning what it held.
+    pub fn remove(&mut self, pos: impl Into<Point<i64>>) -> Option<T> {
+        self.cells.remove(&pos.into())
+    }
+
+    /// Every occupied cell, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i64>, &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    /// The positions of every occupied cell, in no particular order.
+    pub fn positions(&self) -> impl Iterator<Item = Point<i64>> {
+        self.cells.keys().copied()
+    }
+
+    /// The box around every occupied cell, or `None` if there are none.
+    #[must_use]
+    pub fn bounds(&self) -> Option<Bounds> {
+        let mut positions = self.positions();
+        let first = positions.next()?;
+
+        Some(positions.fold(
+            Bounds {
+                min: first,
+                max: first,
+            },
+            |bounds, pos| Bounds {
+                min: Point::new(bounds.min.x.min(pos.x), bounds.min.y.min(pos.y)),
+                max: Point::new(bounds.max.x.max(pos.x), bounds.max.y.max(pos.y)),
+            },
+        ))
+    }
+
+    /// The 4 positions next to `pos` (see [`Direction::cardinal`]).
+    pub fn neighbors(&self, pos: impl Into<Point<i64>>) -> impl Iterator<Item = Point<i64>> {
+        let pos = pos.into();
+        Direction::cardinal()
+            .into_iter()
+            .map(move |direction| pos + direction)
+    }
+
+    /// The 8 positions around `pos`, diagonals included (see [`Direction::all`]).
+    pub fn all_neighbors(&self, pos: impl Into<Point<i64>>) -> impl Iterator<Item = Point<i64>> {
+        let pos = pos.into();
+        Direction::all()
+            .into_iter()
+            .map(move |direction| pos + direction)
+    }
+
+    /// Draws the bounding box, one line per row, with `glyph` picking each cell's
+    /// character. An empty grid renders as an empty string.
+    pub fn render(&self, glyph: impl Fn(&T) -> char) -> String {
+        self.bounds()
+            .map(|bounds| self.render_bounds(bounds, glyph))
+            .unwrap_or_default()
+    }
+
+    /// Draws a fixed area, e.g. to keep every frame of an animation the same size.
+    pub fn render_bounds(&self, bounds: Bounds, glyph: impl Fn(&T) -> char) -> String {
+        let mut text = String::new();
+
+        for y in bounds.min.y..=bounds.max.y {
+            if y > bounds.min.y {
+                text.push('\n');
+            }
+            for x in bounds.min.x..=bounds.max.x {
+                text.push(glyph(self.get((x, y))));
+            }
+        }
+
+        text
+    }
+}
+
+impl<T: PartialEq> SparseGrid<T> {
+    /// Parses a character map, one row per line, with `(0, 0)` at the first
+    /// character. Cells that map to `default` are not stored.
+    pub fn parse(input: &str, default: T, mut cell: impl FnMut(char) -> T) -> Self {
+        let mut grid = Self::new(default);
+
+        for (y, line) in (0..).zip(input.lines()) {
+            for (x, c) in (0..).zip(line.chars()) {
+                grid.set((x, y), cell(c));
+            }
+        }
+
+        grid
+    }
+
+    /// Sets the cell at `pos`. Setting it to the default value clears it, so it no
+    /// longer counts as occupied.
+    pub fn set(&mut self, pos: impl Into<Point<i64>>, value: T) {
+        let pos = pos.into();
+
+        if value == self.default {
+            self.cells.remove(&pos);
+        } else {
+            self.cells.insert(pos, value);
+        }
+    }
+
+    /// Changes the value of unoccupied cells, e.g. when the infinite background of an
+    /// image flips. Cells holding the new default are cleared.
+    pub fn set_default(&mut self, default: T) {
+        self.cells.retain(|_, cell| *cell != default);
+        self.default = default;
+    }
+}
+
+impl<T> Index<Point<i64>> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point<i64>) -> &T {
+        self.get(pos)
+    }
+}
+
+impl<T> Index<(i64, i64)> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (i64, i64)) -> &T {
+        self.get(pos)
+    }
+}
+
+/// The bounding box, drawn with each cell's [`GridCell`] glyph.
+impl<T: GridCell> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(GridCell::glyph))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_and_get() {
+        let mut grid = SparseGrid::new('.');
+        grid.set((-2, 5), '#');
+        grid.set((3, -1), 'o');
+
+        assert_eq!(grid[(-2, 5)], '#');
+        assert_eq!(grid[Point::new(0, 0)], '.');
+        assert_eq!(grid.len(), 2);
+
+        grid.set((3, -1), '.');
+        assert!(!grid.is_occupied((3, -1)));
+        assert_eq!(grid.remove((-2, 5)), Some('#'));
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new(false);
+        assert_eq!(grid.bounds(), None);
+
+        grid.set((-2, 5), true);
+        grid.set((3, -1), true);
+
+        let bounds = grid.bounds().unwrap();
+        assert_eq!(bounds.min, Point::new(-2, -1));
+        assert_eq!(bounds.max, Point::new(3, 5));
+        assert_eq!((bounds.width(), bounds.height()), (6, 7));
+        assert_eq!(bounds.pad(1).points().count(), 8 * 9);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = SparseGrid::new(0);
+
+        assert_eq!(
+            grid.neighbors((0, 0)).collect::<Vec<_>>(),
+            [
+                Point::new(0, -1),
+                Point::new(0, 1),
+                Point::new(-1, 0),
+                Point::new(1, 0)
+            ]
+        );
+        assert_eq!(grid.all_neighbors((0, 0)).count(), 8);
+    }
+
+    #[test]
+    fn test_render() {
+        let mut grid = SparseGrid::parse(".#.\n..#\n###\n", false, |c| c == '#');
+        assert_eq!(grid.len(), 5);
+
+        grid.set((-1, 0), true);
+        assert_eq!(grid.to_string(), "#.#.\n...#\n.###");
+        assert_eq!(
+            grid.render(|&alive| if alive { '@' } else { ' ' }),
+            "@ @ \n   @\n @@@"
+        );
+    }
+
+    #[test]
+    fn test_set_default() {
+        let mut image = SparseGrid::parse("#.\n", false, |c| c == '#');
+        image.set((1, 0), true);
+        image.set((5, 5), true);
+        image.remove((5, 5));
+
+        image.set_default(true);
+        assert!(image.is_empty());
+        assert!(image[(100, -100)]);
+    }
+}
@@ -0,0 +1,263 @@
+//! Example-driven tests for solutions.
+//!
+//! ```ignore
+//! #[cfg(test)]
+//! mod tests {
+//!     use super::*;
+//!
+//!     utils::aoc_tests!(Day05,
+//!         example("ex1.txt") => part1 = 143, part2 = 123;
+//!         example(inline "47|53\n97|13") => part2 = 4;
+//!         example("ex2.txt") with Params { size: 7 } => part1 = 22;
+//!     );
+//! }
+//! ```
+//!
+//! Each example becomes a module (`example1`, `example2`, ...) with one `#[test]`
+//! per listed part, e.g. `tests::example1::part2`. Example files are read relative
+//! to the day's crate directory; `with` parses using other puzzle parameters.
+//!
+//! Answers are compared with `==` when the expected value has a comparable type
+//! (`143` for a `u64`, `"abc"` for a `String`), and by their `Display` output
+//! otherwise, e.g. a [`crate::Grid`] against its drawing.
+
+use std::fmt::{Display, Write};
+
+/// Compares an answer with the expected one by their `Display` output, panicking
+/// with a readable diff if they differ. Multi-line answers are compared per line.
+#[track_caller]
+pub fn check_answer(context: &str, actual: impl Display, expected: impl Display) {
+    let actual = actual.to_string();
+    let expected = expected.to_string();
+
+    if actual != expected {
+        panic!("{}", answer_diff(context, &expected, &actual));
+    }
+}
+
+/// An answer to check in `aoc_tests!`. Calling `(&check).check()` picks
+/// [`CheckTyped`] if the answer can be compared with the expected value, and
+/// [`CheckDisplayed`] otherwise.
+#[doc(hidden)]
+pub struct AnswerCheck<'a, A, E> {
+    pub context: &'a str,
+    pub actual: A,
+    pub expected: E,
+}
+
+#[doc(hidden)]
+pub trait CheckTyped {
+    fn check(&self);
+}
+
+impl<A: PartialEq<E> + Display, E: Display> CheckTyped for AnswerCheck<'_, A, E> {
+    fn check(&self) {
+        if self.actual != self.expected {
+            let expected = self.expected.to_string();
+            let actual = self.actual.to_string();
+            panic!("{}", answer_diff(self.context, &expected, &actual));
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait CheckDisplayed {
+    fn check(&self);
+}
+
+impl<A: Display, E: Display> CheckDisplayed for &AnswerCheck<'_, A, E> {
+    fn check(&self) {
+        check_answer(self.context, &self.actual, &self.expected);
+    }
+}
+
+fn answer_diff(context: &str, expected: &str, actual: &str) -> String {
+    let mut diff = format!("{context}: wrong answer\n");
+
+    if !expected.contains('\n') && !actual.contains('\n') {
+        let _ = write!(diff, "  expected: {expected}\n    actual: {actual}");
+        return diff;
+    }
+
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    for index in 0..expected.len().max(actual.len()) {
+        match (expected.get(index), actual.get(index)) {
+            (Some(expected), Some(actual)) if expected == actual => {
+                let _ = writeln!(diff, "    {expected}");
+            }
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    let _ = writeln!(diff, "  - {expected}");
+                }
+                if let Some(actual) = actual {
+                    let _ = writeln!(diff, "  + {actual}");
+                }
+            }
+        }
+    }
+
+    diff.push_str("  (- expected, + actual)");
+    diff
+}
+
+/// Generates example tests for a solution; see the [module docs](self).
+#[macro_export]
+macro_rules! aoc_tests {
+    ($day:expr, $($examples:tt)*) => {
+        $crate::aoc_tests!(
+            @munch $day;
+            [
+                example1 example2 example3 example4 example5 example6 example7 example8
+                example9 example10 example11 example12 example13 example14 example15 example16
+            ];
+            $($examples)*
+        );
+    };
+
+    (@munch $day:expr; [$($names:ident)*];) => {};
+    (@munch $day:expr; [];  $($rest:tt)+) => {
+        compile_error!("aoc_tests! supports at most 16 examples");
+    };
+    (
+        @munch $day:expr; [$name:ident $($names:ident)*];
+        example($($source:tt)+) with $params:expr => $($part:ident = $expected:expr),+ ;
+        $($rest:tt)*
+    ) => {
+        $crate::aoc_tests!(@example $day; $name; ($($source)+); $params; $($part = $expected),+);
+        $crate::aoc_tests!(@munch $day; [$($names)*]; $($rest)*);
+    };
+    (
+        @munch $day:expr; [$name:ident $($names:ident)*];
+        example($($source:tt)+) => $($part:ident = $expected:expr),+ ;
+        $($rest:tt)*
+    ) => {
+        $crate::aoc_tests!(
+            @example $day; $name; ($($source)+); ::core::default::Default::default();
+            $($part = $expected),+
+        );
+        $crate::aoc_tests!(@munch $day; [$($names)*]; $($rest)*);
+    };
+
+    (@example $day:expr; $name:ident; $source:tt; $params:expr; $($part:ident = $expected:expr),+) => {
+        mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            $(
+                #[test]
+                fn $part() {
+                    let (label, input) = $crate::aoc_tests!(@source $name $source);
+                    let day = $day;
+                    let parsed = $crate::SolutionExt::parse_input_with(&day, &input, &$params);
+                    let actual = $crate::aoc_tests!(@part day parsed $part);
+
+                    #[allow(unused_imports)]
+                    use $crate::{CheckDisplayed as _, CheckTyped as _};
+                    (&$crate::AnswerCheck {
+                        context: &format!("{label} {}", stringify!($part)),
+                        actual,
+                        expected: $expected,
+                    })
+                        .check();
+                }
+            )+
+        }
+    };
+
+    (@source $name:ident (inline $text:literal)) => {
+        (stringify!($name).to_string(), String::from($text))
+    };
+    (@source $name:ident ($path:literal)) => {
+        (
+            format!("{} ({})", stringify!($name), $path),
+            std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path))
+                .unwrap_or_else(|err| panic!("Failed to read example {}: {err}", $path)),
+        )
+    };
+
+    (@part $day:ident $parsed:ident part1) => {
+        $crate::SolutionExt::part1(&$day, &$parsed)
+    };
+    (@part $day:ident $parsed:ident part2) => {
+        $crate::SolutionExt::part2(&$day, &$parsed)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Solution, SolutionError, try_parse_lines};
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<i32>;
+        type Output1 = i32;
+        type Output2 = String;
+        type Params = ();
+
+        fn try_parse_input(&self, input: &str) -> Result<Self::Input, SolutionError> {
+            try_parse_lines(input)
+        }
+
+        fn try_part1(&self, input: &Self::Input) -> Result<Self::Output1, SolutionError> {
+            Ok(input.iter().sum())
+        }
+
+        fn try_part2(&self, input: &Self::Input) -> Result<Self::Output2, SolutionError> {
+            let lines: String = input.iter().map(|n| format!("{n}\n")).collect();
+            Ok(lines.trim_end().to_string())
+        }
+    }
+
+    crate::aoc_tests!(Sum,
+        example(inline "1\n2\n3") => part1 = 6, part2 = "1\n2\n3";
+        example(inline "") => part1 = 0;
+    );
+
+    #[test]
+    fn test_single_line_diff() {
+        assert_eq!(
+            answer_diff("example1 part1", "143", "150"),
+            "example1 part1: wrong answer\n  expected: 143\n    actual: 150"
+        );
+    }
+
+    #[test]
+    fn test_multi_line_diff() {
+        assert_eq!(
+            answer_diff("example1 part2", "##\n#.", "##\n.."),
+            "example1 part2: wrong answer\n    ##\n  - #.\n  + ..\n  (- expected, + actual)"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "wrong answer")]
+    fn test_check_answer_panics_on_mismatch() {
+        check_answer("example1 part1", 1, 2);
+    }
+
+    #[test]
+    fn test_comparable_answers_are_compared_by_value() {
+        // Equal values that display differently
+        AnswerCheck {
+            context: "example1 part1",
+            actual: -0.0,
+            expected: 0.0,
+        }
+        .check();
+    }
+
+    #[test]
+    fn test_other_answers_are_compared_by_display() {
+        let grid = crate::Grid::parse("#.\n.#", |c| c).unwrap();
+        (&AnswerCheck {
+            context: "example1 part2",
+            actual: grid,
+            expected: "#.\n.#",
+        })
+            .check();
+    }
+}
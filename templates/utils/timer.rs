@@ -0,0 +1,106 @@
+//! Splits the time of a combined run between part 1 and part 2.
+
+use std::fmt::Display;
+use std::time::{Duration, Instant};
+
+use crate::spans::{Span, span};
+
+/// Passed to [`crate::Solution::try_solve_both`]; call [`PartTimer::part1_done`]
+/// once part 1's answer is known, so everything before goes to part 1 (parsing
+/// included) and everything after to part 2.
+pub struct PartTimer {
+    start: Instant,
+    split: Option<Instant>,
+    span: Option<Span>,
+    part1_answer: Option<String>,
+}
+
+impl PartTimer {
+    /// Starts timing part 1.
+    #[must_use]
+    pub fn start() -> Self {
+        Self {
+            span: Some(span("part 1")),
+            start: Instant::now(),
+            split: None,
+            part1_answer: None,
+        }
+    }
+
+    /// Marks the end of part 1 and the start of part 2. Later calls are ignored.
+    pub fn part1_done(&mut self) {
+        if self.split.is_some() {
+            return;
+        }
+
+        self.split = Some(Instant::now());
+
+        // End the part 1 span before opening part 2, so they are siblings
+        drop(self.span.take());
+        self.span = Some(span("part 2"));
+    }
+
+    /// Like [`PartTimer::part1_done`], also keeping part 1's answer so it can still be
+    /// shown if part 2 then fails.
+    pub fn part1_done_with(&mut self, answer: &impl Display) {
+        if self.split.is_none() {
+            self.part1_answer = Some(answer.to_string());
+        }
+
+        self.part1_done();
+    }
+
+    /// Part 1's answer, if given to [`PartTimer::part1_done_with`].
+    pub(crate) fn take_part1_answer(&mut self) -> Option<String> {
+        self.part1_answer.take()
+    }
+
+    /// The part currently being timed, used to label errors.
+    #[must_use]
+    pub const fn stage(&self) -> &'static str {
+        if self.split.is_some() {
+            "part 2"
+        } else {
+            "part 1"
+        }
+    }
+
+    /// Stops the timer and returns the time spent on each part. If part 1 was never
+    /// marked done, all of the time is attributed to it.
+    #[must_use]
+    pub fn finish(mut self) -> (Duration, Duration) {
+        let end = Instant::now();
+        drop(self.span.take());
+
+        let split = self.split.unwrap_or(end);
+        (split - self.start, end - split)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsplit_time_goes_to_part1() {
+        let timer = PartTimer::start();
+        std::thread::sleep(Duration::from_millis(2));
+
+        let (part1, part2) = timer.finish();
+        assert!(part1 >= Duration::from_millis(2));
+        assert!(part2.is_zero());
+    }
+
+    #[test]
+    fn test_split_attributes_both_parts() {
+        let mut timer = PartTimer::start();
+        std::thread::sleep(Duration::from_millis(2));
+        timer.part1_done();
+        assert_eq!(timer.stage(), "part 2");
+        std::thread::sleep(Duration::from_millis(2));
+
+        let (part1, part2) = timer.finish();
+        assert!(part1 >= Duration::from_millis(2));
+        assert!(part2 >= Duration::from_millis(2));
+    }
+}
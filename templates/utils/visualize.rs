@@ -0,0 +1,133 @@
+//! Frames for watching a solution work, instead of scattering `println!` through it.
+//!
+//! ```ignore
+//! for step in 0..steps {
+//!     simulate(&mut grid);
+//!     utils::frame_grid(&format!("step {step}"), &grid, [guard]);
+//! }
+//! utils::frame_text("done", format!("visited {} cells", visited.len()));
+//! ```
+//!
+//! Frames are only emitted under `aoc-cli visualize` (`--visualize`), which plays
+//! them back in the terminal or exports them. Otherwise the calls do nothing; guard
+//! expensive frame preparation with [`visualizing`].
+
+use std::fmt::{Display, Write};
+use std::io::Write as _;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// A grid cell that can be drawn as a single character.
+pub trait GridCell {
+    fn glyph(&self) -> char;
+}
+
+impl GridCell for char {
+    fn glyph(&self) -> char {
+        *self
+    }
+}
+
+impl GridCell for u8 {
+    fn glyph(&self) -> char {
+        char::from(*self)
+    }
+}
+
+impl GridCell for bool {
+    fn glyph(&self) -> char {
+        if *self { '#' } else { '.' }
+    }
+}
+
+impl<T: GridCell + ?Sized> GridCell for &T {
+    fn glyph(&self) -> char {
+        (**self).glyph()
+    }
+}
+
+/// Turns frame output on. Called by `run_solution!` when given `--visualize`.
+pub fn enable_visualization() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+#[must_use]
+pub fn visualizing() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Emits a frame of free-form text.
+pub fn frame_text(title: &str, text: impl Display) {
+    if !visualizing() {
+        return;
+    }
+
+    emit("text", title, &text.to_string(), &[]);
+}
+
+/// Emits a grid snapshot, with the cells at the given `(x, y)` positions highlighted.
+pub fn frame_grid<G, R, C>(
+    title: &str,
+    grid: G,
+    highlights: impl IntoIterator<Item = (usize, usize)>,
+) where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = C>,
+    C: GridCell,
+{
+    if !visualizing() {
+        return;
+    }
+
+    let mut rows = String::new();
+    for row in grid {
+        rows.extend(row.into_iter().map(|cell| cell.glyph()));
+        rows.push('\n');
+    }
+
+    let highlights: Vec<(usize, usize)> = highlights.into_iter().collect();
+    emit("grid", title, &rows, &highlights);
+}
+
+/// Writes one frame for the CLI:
+///
+/// ```text
+/// FRAME:<kind>:<title>
+/// HIGHLIGHT:<x>,<y> <x>,<y> ...
+/// |<line>
+/// FRAME_END
+/// ```
+fn emit(kind: &str, title: &str, body: &str, highlights: &[(usize, usize)]) {
+    let mut frame = format!("FRAME:{kind}:{}\n", title.replace('\n', " "));
+
+    if !highlights.is_empty() {
+        frame.push_str("HIGHLIGHT:");
+        for (index, (x, y)) in highlights.iter().enumerate() {
+            let separator = if index == 0 { "" } else { " " };
+            let _ = write!(frame, "{separator}{x},{y}");
+        }
+        frame.push('\n');
+    }
+
+    for line in body.lines() {
+        let _ = writeln!(frame, "|{line}");
+    }
+    frame.push_str("FRAME_END\n");
+
+    // One write per frame keeps frames whole even with a lot of them
+    let _ = std::io::stdout().lock().write_all(frame.as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyphs() {
+        assert_eq!('#'.glyph(), '#');
+        assert_eq!(b'.'.glyph(), '.');
+        assert_eq!(true.glyph(), '#');
+        assert_eq!(GridCell::glyph(&&false), '.');
+    }
+}